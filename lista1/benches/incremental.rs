@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use lista1::generators::grid_with_diagonals;
+use lista1::incremental::IncrementalTopologicalOrder;
+use lista1::Graph;
+
+fn shuffled_edges(n: usize) -> (usize, Vec<(usize, usize)>) {
+    let graph = grid_with_diagonals(n);
+    let mut edges = graph.edges;
+    edges.shuffle(&mut StdRng::seed_from_u64(0));
+    (graph.n, edges)
+}
+
+fn insert_all(n: usize, edges: &[(usize, usize)]) -> bool {
+    let mut dag = IncrementalTopologicalOrder::new(n);
+    edges.iter().all(|&(u, v)| dag.insert_edge(u, v).is_ok())
+}
+
+fn recompute_after_each(n: usize, edges: &[(usize, usize)]) -> bool {
+    let mut graph: Graph<()> = Graph::new(n);
+    edges.iter().all(|&(u, v)| {
+        graph.add_edge(u, v);
+        graph.topological_sort().is_some()
+    })
+}
+
+fn bench_insertions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("g2a insertions");
+    group.sample_size(10);
+
+    for n in [100, 400, 1600] {
+        let (n, edges) = shuffled_edges(n);
+        group.bench_with_input(BenchmarkId::new("pearce-kelly", n), &edges, |b, edges| {
+            b.iter(|| assert!(insert_all(n, edges)))
+        });
+        group.bench_with_input(BenchmarkId::new("recompute", n), &edges, |b, edges| {
+            b.iter(|| assert!(recompute_after_each(n, edges)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_insertions);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+use std::env;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+
+use lista1::generators::generate;
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+    if args.len() < 3 || args.len() > 5 {
+        println!("Usage: {} <family> <n> [seed] [file_path]", args[0]);
+        println!("Families: g2a g2b g3 u4a u4b d4a d4b");
+        return;
+    }
+
+    let family = &args[1];
+    let n = match args[2].parse::<usize>() {
+        Ok(n) => n,
+        Err(e) => {
+            println!("ERROR: invalid size {}: {}", args[2], e);
+            return;
+        }
+    };
+    let seed = match args.get(3).map(|s| s.parse::<u64>()) {
+        Some(Ok(seed)) => seed,
+        Some(Err(e)) => {
+            println!("ERROR: invalid seed {}: {}", args[3], e);
+            return;
+        }
+        None => 0,
+    };
+
+    let graph = match generate(family, n, seed) {
+        Some(graph) => graph,
+        None => {
+            println!("Unknown family: {}", family);
+            return;
+        }
+    };
+
+    let mut writer: BufWriter<Box<dyn Write>> = match args.get(4) {
+        Some(file_path) => match File::create(file_path) {
+            Ok(file) => BufWriter::new(Box::new(file)),
+            Err(e) => {
+                println!("ERROR: {}", e);
+                return;
+            }
+        },
+        None => BufWriter::new(Box::new(stdout().lock())),
+    };
+
+    if let Err(e) = graph.write_to(&mut writer).and_then(|_| writer.flush()) {
+        println!("ERROR: {}", e);
+    }
+}
@@ -0,0 +1,228 @@
+use std::io::{self, Write};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::Graph;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeList {
+    pub directed: bool,
+    pub n: usize,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl EdgeList {
+    fn new(directed: bool, n: usize) -> EdgeList {
+        EdgeList {
+            directed,
+            n,
+            edges: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, u: usize, v: usize) {
+        self.edges.push((u, v));
+    }
+
+    /// Writes the graph in the lab format, with vertices numbered from 1.
+    pub fn write_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w, "{}", if self.directed { "D" } else { "U" })?;
+        writeln!(w, "{}", self.n)?;
+        writeln!(w, "{}", self.edges.len())?;
+        for &(u, v) in self.edges.iter() {
+            writeln!(w, "{} {}", u + 1, v + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Builds a `Graph`, adding both directions of every edge for undirected graphs.
+    pub fn to_graph<T>(&self) -> Graph<T> {
+        let mut graph = Graph::new(self.n);
+        for &(u, v) in self.edges.iter() {
+            graph.add_edge(u, v);
+            if !self.directed {
+                graph.add_edge(v, u);
+            }
+        }
+        graph
+    }
+}
+
+fn grid_side(n: usize) -> usize {
+    ((n as f64).sqrt().round() as usize).max(2)
+}
+
+fn push_grid(g: &mut EdgeList, k: usize) {
+    for r in 0..k {
+        for c in 0..k {
+            let u = r * k + c;
+            if c + 1 < k {
+                g.push(u, u + 1);
+            }
+            if r + 1 < k {
+                g.push(u, u + k);
+            }
+        }
+    }
+}
+
+fn middle_cell(k: usize) -> usize {
+    let m = (k - 1) / 2;
+    m * k + m
+}
+
+/// `g2a`: directed `k x k` grid with diagonals, acyclic. `n` is rounded to a square.
+pub fn grid_with_diagonals(n: usize) -> EdgeList {
+    let k = grid_side(n);
+    let mut g = EdgeList::new(true, k * k);
+    push_grid(&mut g, k);
+    for r in 0..k - 1 {
+        for c in 0..k - 1 {
+            let u = r * k + c;
+            g.push(u, u + k + 1);
+        }
+    }
+    g
+}
+
+/// `g2b`: like `g2a`, but the diagonal of the middle cell goes both ways, creating a cycle.
+pub fn grid_with_diagonals_and_cycle(n: usize) -> EdgeList {
+    let mut g = grid_with_diagonals(n);
+    let k = grid_side(n);
+    let u = middle_cell(k);
+    g.push(u + k + 1, u);
+    g
+}
+
+/// `d4a`: directed `k x k` grid. `n` is rounded to a square.
+pub fn grid(n: usize) -> EdgeList {
+    let k = grid_side(n);
+    let mut g = EdgeList::new(true, k * k);
+    push_grid(&mut g, k);
+    g
+}
+
+/// `d4b`: directed grid with a single diagonal in the middle cell.
+pub fn grid_with_center_diagonal(n: usize) -> EdgeList {
+    let mut g = grid(n);
+    let k = grid_side(n);
+    let u = middle_cell(k);
+    g.push(u, u + k + 1);
+    g
+}
+
+/// `u4a`/`u4b`: full binary tree whose leaves are linked to their ancestor `up` levels higher.
+///
+/// The tree has as many levels as needed to reach about `n` vertices.
+pub fn binary_tree_with_leaf_links(n: usize, up: usize) -> EdgeList {
+    let levels = (((n + 1) as f64).log2().round() as usize).max(up + 1);
+    let size = (1 << levels) - 1;
+    let mut g = EdgeList::new(false, size);
+    for u in 0..size / 2 {
+        g.push(u, 2 * u + 1);
+        g.push(u, 2 * u + 2);
+    }
+    for leaf in size / 2..size {
+        let mut ancestor = leaf;
+        for _ in 0..up {
+            ancestor = (ancestor - 1) / 2;
+        }
+        g.push(ancestor, leaf);
+    }
+    g
+}
+
+/// `g3`: five strongly connected components `K -> G -> P -> t` and `K -> C -> t`.
+///
+/// `K` is a small clique, `G` a strongly connected grid, `P` a two-way path, `C` a cycle
+/// and `t` a single sink. The arcs between components are picked from `seed`.
+///
+/// The graph has exactly `n` vertices for `n >= 14`; smaller `n` are raised to 14, the
+/// fewest that leave every component its minimal size.
+pub fn five_components(n: usize, seed: u64) -> EdgeList {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let k = 5;
+    // `G`, `P` and `C` need at least 4, 2 and 2 vertices
+    let rest = n.saturating_sub(k + 1).max(8);
+    // even side, so that the alternating grid is strongly connected
+    let a = ((((rest / 3) as f64).sqrt() as usize) & !1).max(2);
+    let p = ((rest - a * a) / 2).max(2);
+    let c = (rest - a * a - p).max(2);
+
+    let k_start = 0;
+    let g_start = k_start + k;
+    let p_start = g_start + a * a;
+    let c_start = p_start + p;
+    let t = c_start + c;
+
+    let mut g = EdgeList::new(true, t + 1);
+
+    for u in 0..k {
+        for v in 0..k {
+            if u != v {
+                g.push(k_start + u, k_start + v);
+            }
+        }
+    }
+
+    for r in 0..a {
+        for col in 0..a {
+            let u = g_start + r * a + col;
+            if col + 1 < a {
+                if r % 2 == 0 {
+                    g.push(u, u + 1);
+                } else {
+                    g.push(u + 1, u);
+                }
+            }
+            if r + 1 < a {
+                if col % 2 == 0 {
+                    g.push(u + a, u);
+                } else {
+                    g.push(u, u + a);
+                }
+            }
+        }
+    }
+
+    for i in 0..p - 1 {
+        g.push(p_start + i, p_start + i + 1);
+        g.push(p_start + i + 1, p_start + i);
+    }
+
+    for i in 0..c {
+        g.push(c_start + i, c_start + (i + 1) % c);
+    }
+
+    let mut connect = |g: &mut EdgeList, from: (usize, usize), to: (usize, usize)| {
+        for _ in 0..rng.gen_range(1..=3) {
+            let u = from.0 + rng.gen_range(0..from.1);
+            let v = to.0 + rng.gen_range(0..to.1);
+            g.push(u, v);
+        }
+    };
+
+    connect(&mut g, (k_start, k), (g_start, a * a));
+    connect(&mut g, (k_start, k), (c_start, c));
+    connect(&mut g, (g_start, a * a), (p_start, p));
+    connect(&mut g, (p_start, p), (t, 1));
+    connect(&mut g, (c_start, c), (t, 1));
+
+    g
+}
+
+/// Generates a graph of the family named like the `aod_testy1` files.
+pub fn generate(family: &str, n: usize, seed: u64) -> Option<EdgeList> {
+    match family {
+        "g2a" => Some(grid_with_diagonals(n)),
+        "g2b" => Some(grid_with_diagonals_and_cycle(n)),
+        "g3" => Some(five_components(n, seed)),
+        "u4a" => Some(binary_tree_with_leaf_links(n, 3)),
+        "u4b" => Some(binary_tree_with_leaf_links(n, 2)),
+        "d4a" => Some(grid(n)),
+        "d4b" => Some(grid_with_center_diagonal(n)),
+        _ => None,
+    }
+}
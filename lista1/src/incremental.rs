@@ -0,0 +1,252 @@
+use std::mem;
+
+/// Topological order of a directed graph maintained under edge insertions (Pearce–Kelly).
+///
+/// Vertices may be merged into strongly connected components by
+/// [`insert_edge_merging`](Self::insert_edge_merging); the order is then kept over
+/// components, each identified by a representative vertex.
+pub struct IncrementalTopologicalOrder {
+    ord: Vec<usize>,
+    adj: Vec<Vec<usize>>,
+    radj: Vec<Vec<usize>>,
+    representative: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    visited: Vec<bool>,
+    pred: Vec<usize>,
+}
+
+impl IncrementalTopologicalOrder {
+    pub fn new(n: usize) -> IncrementalTopologicalOrder {
+        IncrementalTopologicalOrder {
+            ord: (0..n).collect(),
+            adj: vec![Vec::new(); n],
+            radj: vec![Vec::new(); n],
+            representative: (0..n).collect(),
+            members: (0..n).map(|u| vec![u]).collect(),
+            visited: vec![false; n],
+            pred: vec![usize::MAX; n],
+        }
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.ord.len()
+    }
+
+    /// Representative vertex of the component containing `u`.
+    pub fn component(&self, u: usize) -> usize {
+        self.representative[u]
+    }
+
+    pub fn component_members(&self, u: usize) -> &[usize] {
+        &self.members[self.representative[u]]
+    }
+
+    /// Returns the order in the format of `Graph::topological_sort`: `order[u]` is the
+    /// 1-based position of `u`. Vertices of one component share a position.
+    pub fn order(&self) -> Vec<usize> {
+        let mut components: Vec<usize> = (0..self.get_n())
+            .filter(|&u| self.representative[u] == u)
+            .collect();
+        components.sort_unstable_by_key(|&c| self.ord[c]);
+
+        let mut position = vec![0; self.get_n()];
+        for (i, &c) in components.iter().enumerate() {
+            position[c] = i + 1;
+        }
+        (0..self.get_n())
+            .map(|u| position[self.representative[u]])
+            .collect()
+    }
+
+    /// Inserts the edge `u -> v` unless it would close a cycle.
+    ///
+    /// On failure the graph is left unchanged and the cycle `u -> v -> ... -> u` is returned
+    /// as a list of components, starting with `u`'s.
+    pub fn insert_edge(&mut self, u: usize, v: usize) -> Result<(), Vec<usize>> {
+        let (cu, cv) = (self.representative[u], self.representative[v]);
+        if u == v {
+            return Err(vec![cu]);
+        }
+        if cu == cv || self.ord[cu] < self.ord[cv] {
+            self.push_edge(u, v);
+            return Ok(());
+        }
+
+        let (forward, found) = self.search_forward(cv, cu, true);
+        if found {
+            let mut cycle = vec![cu];
+            let mut c = cu;
+            while c != cv {
+                c = self.pred[c];
+                cycle.push(c);
+            }
+            cycle[1..].reverse();
+            self.clear_visited(&forward);
+            return Err(cycle);
+        }
+
+        let backward = self.search_backward(cu, cv);
+        self.clear_visited(&forward);
+        self.clear_visited(&backward);
+        self.reorder(backward, None, forward);
+        self.push_edge(u, v);
+        Ok(())
+    }
+
+    /// Inserts the edge `u -> v`, merging every component on a newly closed cycle into one.
+    ///
+    /// Returns the members of the merged component if a cycle was closed.
+    pub fn insert_edge_merging(&mut self, u: usize, v: usize) -> Option<Vec<usize>> {
+        let (cu, cv) = (self.representative[u], self.representative[v]);
+        if cu == cv || self.ord[cu] < self.ord[cv] {
+            self.push_edge(u, v);
+            return None;
+        }
+
+        let (forward, found) = self.search_forward(cv, cu, false);
+        self.clear_visited(&forward);
+        let backward = self.search_backward(cu, cv);
+        self.clear_visited(&backward);
+
+        if !found {
+            self.reorder(backward, None, forward);
+            self.push_edge(u, v);
+            return None;
+        }
+
+        // components both reachable from `v` and reaching `u` form the new cycle
+        for &c in forward.iter() {
+            self.visited[c] = true;
+        }
+        let (cycle, backward): (Vec<usize>, Vec<usize>) =
+            backward.into_iter().partition(|&c| self.visited[c]);
+        for &c in cycle.iter() {
+            self.visited[c] = false;
+        }
+        let forward: Vec<usize> = forward.into_iter().filter(|&c| self.visited[c]).collect();
+        self.clear_visited(&forward);
+
+        let merged = self.merge(&cycle);
+        let mut slots: Vec<usize> = cycle.iter().map(|&c| self.ord[c]).collect();
+        slots.extend(backward.iter().chain(forward.iter()).map(|&c| self.ord[c]));
+        self.reorder_into(backward, Some(merged), forward, slots);
+        self.push_edge(u, v);
+        Some(self.members[merged].clone())
+    }
+
+    fn push_edge(&mut self, u: usize, v: usize) {
+        self.adj[self.representative[u]].push(v);
+        self.radj[self.representative[v]].push(u);
+    }
+
+    fn clear_visited(&mut self, components: &[usize]) {
+        for &c in components {
+            self.visited[c] = false;
+        }
+    }
+
+    // Visits components reachable from `start` with `ord` not above `ord[target]`.
+    fn search_forward(&mut self, start: usize, target: usize, stop: bool) -> (Vec<usize>, bool) {
+        let bound = self.ord[target];
+        let mut found = false;
+        let mut seen = vec![start];
+        let mut stack = vec![start];
+        self.visited[start] = true;
+
+        while let Some(c) = stack.pop() {
+            for &w in self.adj[c].iter() {
+                let w = self.representative[w];
+                if self.visited[w] || self.ord[w] > bound {
+                    continue;
+                }
+                self.visited[w] = true;
+                self.pred[w] = c;
+                seen.push(w);
+                if w == target {
+                    found = true;
+                    if stop {
+                        return (seen, true);
+                    }
+                }
+                stack.push(w);
+            }
+        }
+
+        (seen, found)
+    }
+
+    // Visits components reaching `start` with `ord` not below `ord[target]`.
+    fn search_backward(&mut self, start: usize, target: usize) -> Vec<usize> {
+        let bound = self.ord[target];
+        let mut seen = vec![start];
+        let mut stack = vec![start];
+        self.visited[start] = true;
+
+        while let Some(c) = stack.pop() {
+            for &w in self.radj[c].iter() {
+                let w = self.representative[w];
+                if self.visited[w] || self.ord[w] < bound {
+                    continue;
+                }
+                self.visited[w] = true;
+                seen.push(w);
+                stack.push(w);
+            }
+        }
+
+        seen
+    }
+
+    fn merge(&mut self, cycle: &[usize]) -> usize {
+        let root = *cycle
+            .iter()
+            .max_by_key(|&&c| self.members[c].len())
+            .unwrap();
+        for &c in cycle.iter().filter(|&&c| c != root) {
+            let members = mem::take(&mut self.members[c]);
+            for &u in members.iter() {
+                self.representative[u] = root;
+            }
+            self.members[root].extend(members);
+            let adj = mem::take(&mut self.adj[c]);
+            self.adj[root].extend(adj);
+            let radj = mem::take(&mut self.radj[c]);
+            self.radj[root].extend(radj);
+        }
+        root
+    }
+
+    fn reorder(&mut self, backward: Vec<usize>, merged: Option<usize>, forward: Vec<usize>) {
+        let slots = backward
+            .iter()
+            .chain(forward.iter())
+            .map(|&c| self.ord[c])
+            .collect();
+        self.reorder_into(backward, merged, forward, slots);
+    }
+
+    // Places `backward` into the lowest of `slots`, `forward` into the highest and
+    // `merged` in between, keeping the previous relative order inside each group.
+    fn reorder_into(
+        &mut self,
+        mut backward: Vec<usize>,
+        merged: Option<usize>,
+        mut forward: Vec<usize>,
+        mut slots: Vec<usize>,
+    ) {
+        backward.sort_unstable_by_key(|&c| self.ord[c]);
+        forward.sort_unstable_by_key(|&c| self.ord[c]);
+        slots.sort_unstable();
+
+        let high = slots.split_off(slots.len() - forward.len());
+        for (&c, &slot) in backward.iter().zip(slots.iter()) {
+            self.ord[c] = slot;
+        }
+        if let Some(c) = merged {
+            self.ord[c] = slots[backward.len()];
+        }
+        for (c, slot) in forward.into_iter().zip(high) {
+            self.ord[c] = slot;
+        }
+    }
+}
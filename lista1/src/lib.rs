@@ -1,3 +1,8 @@
+pub mod generators;
+pub mod incremental;
+#[cfg(feature = "serde")]
+mod serialization;
+
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::fs::File;
@@ -27,7 +32,7 @@ pub struct Graph<T> {
 }
 
 impl<T> Graph<T> {
-    fn new(n: usize) -> Graph<T> {
+    pub fn new(n: usize) -> Graph<T> {
         let mut g = Graph {
             n,
             nodes: Vec::new(),
@@ -44,22 +49,109 @@ impl<T> Graph<T> {
         self.n
     }
 
-    fn add_edge(&mut self, u: usize, v: usize) {
+    /// Adds a directed edge `u -> v`. Parallel edges are allowed.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
         let u = self.nodes.get(u).unwrap();
         let v = self.nodes.get(v).unwrap();
         let mut u_mut = u.borrow_mut();
         u_mut.edges.push(v.clone());
     }
 
+    /// Removes one directed edge `u -> v`. Returns `false` if there was no such edge.
+    pub fn remove_edge(&mut self, u: usize, v: usize) -> bool {
+        let u = self.nodes.get(u).unwrap();
+        let v = self.nodes.get(v).unwrap();
+        let mut u_mut = u.borrow_mut();
+        match u_mut.edges.iter().position(|x| Rc::ptr_eq(x, v)) {
+            Some(pos) => {
+                u_mut.edges.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn get_node(&self, index: usize) -> Rc<RefCell<Node<T>>> {
         self.nodes.get(index).unwrap().clone()
     }
 
-    pub fn add_node(&mut self, node: Rc<RefCell<Node<T>>>) {
-        self.nodes.push(node);
+    /// Appends a new isolated node and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        let index = self.n;
+        self.nodes.push(Node::new(index));
         self.n += 1;
+        index
     }
 
+    /// Removes node `index` together with all edges incident to it and returns its data.
+    ///
+    /// Nodes with a greater index are shifted down by one, so indices stay in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not in `0..n`.
+    pub fn remove_node(&mut self, index: usize) -> Option<T> {
+        let removed = self.nodes.remove(index);
+        self.n -= 1;
+
+        for u in self.nodes.iter() {
+            let mut u_mut = u.borrow_mut();
+            u_mut.edges.retain(|v| !Rc::ptr_eq(v, &removed));
+            if u_mut.index > index {
+                u_mut.index -= 1;
+            }
+        }
+
+        let mut removed = removed.borrow_mut();
+        removed.edges.clear();
+        removed.data.take()
+    }
+
+    /// Builds the subgraph induced by `vertices`.
+    ///
+    /// Vertex `vertices[i]` becomes node `i` of the result; node data is not copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vertex is not in `0..n` or appears in `vertices` more than once.
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> Graph<T> {
+        let mut mapping = vec![None; self.n];
+        for (i, &u) in vertices.iter().enumerate() {
+            assert!(mapping[u].is_none(), "vertex {} appears twice", u);
+            mapping[u] = Some(i);
+        }
+
+        let mut sub = Graph::new(vertices.len());
+        for (i, &u) in vertices.iter().enumerate() {
+            let node = self.get_node(u);
+            let node = node.borrow();
+            for v in node.edges.iter() {
+                if let Some(j) = mapping[v.borrow().index] {
+                    sub.add_edge(i, j);
+                }
+            }
+        }
+
+        sub
+    }
+
+    /// Reverses the direction of every edge in place.
+    pub fn reverse(&mut self) {
+        let mut reversed = vec![Vec::new(); self.n];
+        for u in self.nodes.iter() {
+            let edges = std::mem::take(&mut u.borrow_mut().edges);
+            for v in edges {
+                let v_index = v.borrow().index;
+                reversed[v_index].push(u.clone());
+            }
+        }
+
+        for (u, edges) in self.nodes.iter().zip(reversed) {
+            u.borrow_mut().edges = edges;
+        }
+    }
+
+    #[allow(clippy::manual_while_let_some)]
     pub fn dfs_form_node(&self, root: Rc<RefCell<Node<T>>>, f: &mut dyn FnMut(&Node<T>)) {
         let mut visited = vec![false; self.n];
 
@@ -84,6 +176,7 @@ impl<T> Graph<T> {
         }
     }
 
+    #[allow(clippy::manual_while_let_some)]
     pub fn dfs(&self, f: &mut dyn FnMut(&Node<T>)) {
         let mut visited = vec![false; self.n];
 
@@ -120,23 +213,24 @@ impl<T> Graph<T> {
         for i in 0..self.n {
             if !visited[i] {
                 let root = self.get_node(i);
-                let mut stack = vec![root];
-
-                while !stack.is_empty() {
-                    let u = stack.pop().unwrap();
+                let mut stack = vec![(root, None)];
 
+                while let Some((u, parent)) = stack.pop() {
                     let u_mut = u.borrow_mut();
 
-                    if !visited[u_mut.index] {
-                        f(&u_mut);
-                        visited[u_mut.index] = true;
+                    if visited[u_mut.index] {
+                        continue;
+                    }
+                    f(&u_mut);
+                    visited[u_mut.index] = true;
+                    if let Some(parent) = parent {
+                        tree.add_edge(parent, u_mut.index);
                     }
 
                     for v in u_mut.edges.iter() {
                         let v_mut = v.borrow_mut();
                         if !visited[v_mut.index] {
-                            stack.push(v.clone());
-                            tree.add_edge(u_mut.index, v_mut.index);
+                            stack.push((v.clone(), Some(u_mut.index)));
                         }
                     }
                 }
@@ -271,6 +365,7 @@ impl<T> Graph<T> {
         tree
     }
 
+    #[allow(clippy::manual_while_let_some)]
     pub fn topological_sort(&mut self) -> Option<Vec<usize>> {
         let mut indegree = vec![0; self.n];
         let mut order = vec![0; self.n];
@@ -316,6 +411,7 @@ impl<T> Graph<T> {
         }
     }
 
+    #[allow(clippy::manual_while_let_some)]
     pub fn find_strongly_connected_components(&self) -> Vec<Vec<usize>> {
         let mut visited = vec![false; self.n];
         let mut stack = Vec::new();
@@ -374,18 +470,13 @@ impl<T> Graph<T> {
     }
 
     fn transpose(&self) -> Graph<T> {
-        let mut gr = Graph::new(self.n);
-        for i in 0..self.n {
-            let node = self.get_node(i);
-            let node = node.borrow();
-            for v in node.edges.iter() {
-                let v = v.borrow();
-                gr.add_edge(v.index, node.index);
-            }
-        }
+        let vertices: Vec<usize> = (0..self.n).collect();
+        let mut gr = self.induced_subgraph(&vertices);
+        gr.reverse();
         gr
     }
 
+    #[allow(clippy::manual_while_let_some)]
     fn dfs_component_util(&self, u: usize, visited: &mut [bool]) -> Vec<usize> {
         let mut component = Vec::new();
         let mut stack = Vec::new();
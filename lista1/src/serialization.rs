@@ -0,0 +1,57 @@
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::Graph;
+
+// Nodes are stored as their data and the indices of their out-neighbours,
+// so the `Rc` links are rebuilt on load.
+#[derive(serde::Serialize)]
+#[serde(rename = "Node")]
+struct NodeRef<'a, T> {
+    data: &'a Option<T>,
+    edges: Vec<usize>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "Node")]
+struct NodeOwned<T> {
+    data: Option<T>,
+    edges: Vec<usize>,
+}
+
+impl<T: Serialize> Serialize for Graph<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.n))?;
+        for node in self.nodes.iter() {
+            let node = node.borrow();
+            let edges = node.edges.iter().map(|v| v.borrow().index).collect();
+            seq.serialize_element(&NodeRef {
+                data: &node.data,
+                edges,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Graph<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let nodes = Vec::<NodeOwned<T>>::deserialize(deserializer)?;
+        let mut graph = Graph::new(nodes.len());
+
+        for (u, node) in nodes.into_iter().enumerate() {
+            for v in node.edges {
+                if v >= graph.n {
+                    return Err(D::Error::custom(format!(
+                        "edge {} -> {} points outside of a graph with {} nodes",
+                        u, v, graph.n
+                    )));
+                }
+                graph.add_edge(u, v);
+            }
+            graph.get_node(u).borrow_mut().data = node.data;
+        }
+
+        Ok(graph)
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use lista1::{Graph, Node};
+
+struct EdgeFile {
+    directed: bool,
+    n: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl EdgeFile {
+    fn read(path: &Path) -> EdgeFile {
+        let contents = fs::read_to_string(path).unwrap();
+        let mut lines = contents.lines().map(str::trim);
+        let directed = lines.next().unwrap() == "D";
+        let n = lines.next().unwrap().parse().unwrap();
+        let m = lines.next().unwrap().parse().unwrap();
+        let edges = lines
+            .take(m)
+            .map(|line| {
+                let mut it = line.split_whitespace();
+                let u: usize = it.next().unwrap().parse().unwrap();
+                let v: usize = it.next().unwrap().parse().unwrap();
+                (u - 1, v - 1)
+            })
+            .collect();
+        EdgeFile { directed, n, edges }
+    }
+
+    fn arcs(&self) -> Vec<(usize, usize)> {
+        let mut arcs = self.edges.clone();
+        if !self.directed {
+            arcs.extend(self.edges.iter().map(|&(u, v)| (v, u)));
+        }
+        arcs
+    }
+
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adj = vec![Vec::new(); self.n];
+        for (u, v) in self.arcs() {
+            adj[u].push(v);
+        }
+        adj
+    }
+}
+
+fn test_files(dir: &str, prefix: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("aod_testy1")
+        .join(dir);
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            name.starts_with(prefix) && name.ends_with(".txt")
+        })
+        .collect();
+    files.sort();
+    assert!(
+        !files.is_empty(),
+        "no {} files in {}",
+        prefix,
+        dir.display()
+    );
+    files
+}
+
+fn all_files() -> Vec<PathBuf> {
+    let mut files = test_files("2", "g2");
+    files.extend(test_files("3", "g3"));
+    files.extend(test_files("4", ""));
+    files
+}
+
+fn load(path: &Path) -> (EdgeFile, Graph<()>) {
+    let file = EdgeFile::read(path);
+    let graph = Graph::build_from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(graph.get_n(), file.n, "{}", path.display());
+    (file, graph)
+}
+
+fn has_cycle(file: &EdgeFile) -> bool {
+    let adj = file.adjacency();
+    // 0 = white, 1 = on the stack, 2 = done
+    let mut state = vec![0u8; file.n];
+    for root in 0..file.n {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        state[root] = 1;
+        while let Some((u, i)) = stack.pop() {
+            if i < adj[u].len() {
+                stack.push((u, i + 1));
+                let v = adj[u][i];
+                match state[v] {
+                    0 => {
+                        state[v] = 1;
+                        stack.push((v, 0));
+                    }
+                    1 => return true,
+                    _ => {}
+                }
+            } else {
+                state[u] = 2;
+            }
+        }
+    }
+    false
+}
+
+fn check_topological_order(file: &EdgeFile, order: &[usize]) {
+    assert_eq!(order.len(), file.n);
+    let mut seen = vec![false; file.n];
+    for &position in order {
+        assert!(
+            (1..=file.n).contains(&position),
+            "position {} out of range",
+            position
+        );
+        assert!(!seen[position - 1], "position {} used twice", position);
+        seen[position - 1] = true;
+    }
+    for (u, v) in file.arcs() {
+        assert!(
+            order[u] < order[v],
+            "arc {} -> {} goes backwards",
+            u + 1,
+            v + 1
+        );
+    }
+}
+
+fn reachable_within(
+    adj: &[Vec<usize>],
+    start: usize,
+    component: &[usize],
+    inside: &[bool],
+) -> bool {
+    let mut seen = vec![false; adj.len()];
+    let mut count = 1;
+    let mut queue = VecDeque::from([start]);
+    seen[start] = true;
+    while let Some(u) = queue.pop_front() {
+        for &v in adj[u].iter() {
+            if inside[v] && !seen[v] {
+                seen[v] = true;
+                count += 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    count == component.len()
+}
+
+fn check_strongly_connected_components(file: &EdgeFile, components: &[Vec<usize>]) {
+    let mut component_of = vec![usize::MAX; file.n];
+    for (c, component) in components.iter().enumerate() {
+        assert!(!component.is_empty());
+        for &u in component {
+            assert_eq!(
+                component_of[u],
+                usize::MAX,
+                "vertex {} in two components",
+                u + 1
+            );
+            component_of[u] = c;
+        }
+    }
+    assert!(
+        component_of.iter().all(|&c| c != usize::MAX),
+        "vertex without component"
+    );
+
+    let adj = file.adjacency();
+    let mut radj = vec![Vec::new(); file.n];
+    for (u, v) in file.arcs() {
+        radj[v].push(u);
+    }
+
+    let mut inside = vec![false; file.n];
+    for component in components {
+        component.iter().for_each(|&u| inside[u] = true);
+        assert!(reachable_within(&adj, component[0], component, &inside));
+        assert!(reachable_within(&radj, component[0], component, &inside));
+        component.iter().for_each(|&u| inside[u] = false);
+    }
+
+    let condensation = EdgeFile {
+        directed: true,
+        n: components.len(),
+        edges: file
+            .arcs()
+            .into_iter()
+            .map(|(u, v)| (component_of[u], component_of[v]))
+            .filter(|(a, b)| a != b)
+            .collect(),
+    };
+    assert!(!has_cycle(&condensation), "components are not maximal");
+}
+
+fn check_bipartition(file: &EdgeFile, (a, b): &(Vec<usize>, Vec<usize>)) {
+    let mut side = vec![None; file.n];
+    for &u in a {
+        assert!(side[u].is_none(), "vertex {} on both sides", u + 1);
+        side[u] = Some(false);
+    }
+    for &u in b {
+        assert!(side[u].is_none(), "vertex {} on both sides", u + 1);
+        side[u] = Some(true);
+    }
+    assert!(side.iter().all(Option::is_some), "vertex without side");
+    for &(u, v) in file.edges.iter() {
+        assert_ne!(
+            side[u],
+            side[v],
+            "edge {} - {} inside one side",
+            u + 1,
+            v + 1
+        );
+    }
+}
+
+fn is_bipartite(file: &EdgeFile) -> bool {
+    let mut adj = vec![Vec::new(); file.n];
+    for &(u, v) in file.edges.iter() {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    let mut color = vec![None; file.n];
+    for root in 0..file.n {
+        if color[root].is_some() {
+            continue;
+        }
+        color[root] = Some(false);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for &v in adj[u].iter() {
+                match color[v] {
+                    None => {
+                        color[v] = color[u].map(|c| !c);
+                        queue.push_back(v);
+                    }
+                    Some(c) if Some(c) == color[u] => return false,
+                    _ => {}
+                }
+            }
+        }
+    }
+    true
+}
+
+fn check_traversal(file: &EdgeFile, visited: &[usize]) {
+    assert_eq!(visited.len(), file.n);
+    let mut seen = vec![false; file.n];
+    for &u in visited {
+        assert!(!seen[u], "vertex {} visited twice", u + 1);
+        seen[u] = true;
+    }
+}
+
+fn check_tree(file: &EdgeFile, tree: &Graph<()>) {
+    let mut arcs = file.arcs();
+    arcs.sort_unstable();
+
+    assert_eq!(tree.get_n(), file.n);
+    let mut parent = vec![None; file.n];
+    for u in 0..tree.get_n() {
+        let node = tree.get_node(u);
+        for v in node.borrow().edges.iter() {
+            let v = v.borrow().index;
+            assert!(
+                arcs.binary_search(&(u, v)).is_ok(),
+                "tree arc {} -> {} not in graph",
+                u + 1,
+                v + 1
+            );
+            assert!(parent[v].is_none(), "vertex {} has two parents", v + 1);
+            parent[v] = Some(u);
+        }
+    }
+
+    let tree_file = EdgeFile {
+        directed: true,
+        n: file.n,
+        edges: (0..file.n)
+            .filter_map(|v| parent[v].map(|u| (u, v)))
+            .collect(),
+    };
+    assert!(!has_cycle(&tree_file), "tree has a cycle");
+}
+
+#[test]
+fn dfs_and_bfs_visit_every_vertex_once() {
+    for path in all_files() {
+        let (file, graph) = load(&path);
+
+        let mut visited = Vec::new();
+        graph.dfs(&mut |node: &Node<()>| visited.push(node.index));
+        check_traversal(&file, &visited);
+
+        let mut visited = Vec::new();
+        graph.bfs(&mut |node: &Node<()>| visited.push(node.index));
+        check_traversal(&file, &visited);
+    }
+}
+
+#[test]
+fn traversal_trees_are_forests_of_graph_arcs() {
+    for path in all_files() {
+        let (file, graph) = load(&path);
+
+        let mut visited = Vec::new();
+        let tree = graph.dfs_with_tree(&mut |node: &Node<()>| visited.push(node.index));
+        check_traversal(&file, &visited);
+        check_tree(&file, &tree);
+
+        let mut visited = Vec::new();
+        let tree = graph.bfs_with_tree(&mut |node: &Node<()>| visited.push(node.index));
+        check_traversal(&file, &visited);
+        check_tree(&file, &tree);
+    }
+}
+
+#[test]
+fn g2a_graphs_are_acyclic() {
+    for path in test_files("2", "g2a") {
+        let (file, mut graph) = load(&path);
+        assert!(!has_cycle(&file), "{}", path.display());
+        let order = graph.topological_sort();
+        assert!(order.is_some(), "{}", path.display());
+        check_topological_order(&file, &order.unwrap());
+    }
+}
+
+#[test]
+fn g2b_graphs_have_a_cycle() {
+    for path in test_files("2", "g2b") {
+        let (file, mut graph) = load(&path);
+        assert!(has_cycle(&file), "{}", path.display());
+        assert!(graph.topological_sort().is_none(), "{}", path.display());
+    }
+}
+
+#[test]
+fn g3_graphs_have_five_strongly_connected_components() {
+    for path in test_files("3", "g3") {
+        let (file, graph) = load(&path);
+        let components = graph.find_strongly_connected_components();
+        assert_eq!(components.len(), 5, "{}", path.display());
+        check_strongly_connected_components(&file, &components);
+    }
+}
+
+#[test]
+fn strongly_connected_components_are_valid_everywhere() {
+    for path in all_files() {
+        let (file, graph) = load(&path);
+        check_strongly_connected_components(&file, &graph.find_strongly_connected_components());
+    }
+}
+
+#[test]
+fn type_a_graphs_are_bipartite() {
+    for path in test_files("4", "u4a")
+        .into_iter()
+        .chain(test_files("4", "d4a"))
+    {
+        let (file, graph) = load(&path);
+        assert!(is_bipartite(&file), "{}", path.display());
+        let bipartition = graph.get_bipartition();
+        assert!(bipartition.is_some(), "{}", path.display());
+        check_bipartition(&file, &bipartition.unwrap());
+    }
+}
+
+#[test]
+fn type_b_graphs_are_not_bipartite() {
+    for path in test_files("4", "u4b")
+        .into_iter()
+        .chain(test_files("4", "d4b"))
+    {
+        let (file, graph) = load(&path);
+        assert!(!is_bipartite(&file), "{}", path.display());
+        assert!(graph.get_bipartition().is_none(), "{}", path.display());
+    }
+}
@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::Path;
+
+use lista1::generators::{generate, EdgeList};
+
+const FAMILIES: [&str; 7] = ["g2a", "g2b", "g3", "u4a", "u4b", "d4a", "d4b"];
+
+// Direction, vertex count and sorted edges of a graph in the lab format.
+fn parse(text: &str) -> (bool, usize, Vec<(usize, usize)>) {
+    let mut lines = text.lines().map(str::trim);
+    let directed = lines.next().unwrap() == "D";
+    let n = lines.next().unwrap().parse().unwrap();
+    let m = lines.next().unwrap().parse().unwrap();
+    let mut edges: Vec<(usize, usize)> = lines
+        .take(m)
+        .map(|line| {
+            let mut it = line.split_whitespace();
+            let u = it.next().unwrap().parse().unwrap();
+            let v = it.next().unwrap().parse().unwrap();
+            (u, v)
+        })
+        .collect();
+    assert_eq!(edges.len(), m);
+    edges.sort_unstable();
+    (directed, n, edges)
+}
+
+fn written(g: &EdgeList) -> String {
+    let mut bytes = Vec::new();
+    g.write_to(&mut bytes).unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+fn check_vertices_in_range(g: &EdgeList) {
+    for &(u, v) in g.edges.iter() {
+        assert!(
+            u < g.n && v < g.n,
+            "edge {} - {} outside of 0..{}",
+            u,
+            v,
+            g.n
+        );
+    }
+}
+
+#[test]
+fn grids_have_a_square_number_of_vertices() {
+    for (n, k) in [(1, 2), (16, 4), (20, 4), (99, 10), (1000, 32)] {
+        let grid = generate("d4a", n, 0).unwrap();
+        assert_eq!((grid.n, grid.edges.len()), (k * k, 2 * k * (k - 1)));
+        let d4b = generate("d4b", n, 0).unwrap();
+        assert_eq!((d4b.n, d4b.edges.len()), (k * k, 2 * k * (k - 1) + 1));
+
+        let diagonals = 2 * k * (k - 1) + (k - 1) * (k - 1);
+        let g2a = generate("g2a", n, 0).unwrap();
+        assert_eq!((g2a.n, g2a.edges.len()), (k * k, diagonals));
+        let g2b = generate("g2b", n, 0).unwrap();
+        assert_eq!((g2b.n, g2b.edges.len()), (k * k, diagonals + 1));
+
+        for g in [grid, d4b, g2a, g2b] {
+            assert!(g.directed);
+            check_vertices_in_range(&g);
+        }
+    }
+}
+
+#[test]
+fn trees_have_a_full_number_of_levels() {
+    for (n, levels) in [(15, 4), (20, 4), (100, 7), (1000, 10)] {
+        let size = (1 << levels) - 1;
+        let leaves = 1 << (levels - 1);
+        for family in ["u4a", "u4b"] {
+            let g = generate(family, n, 0).unwrap();
+            assert!(!g.directed);
+            assert_eq!(
+                (g.n, g.edges.len()),
+                (size, size - 1 + leaves),
+                "{} {}",
+                family,
+                n
+            );
+            check_vertices_in_range(&g);
+        }
+    }
+    // Leaves need an ancestor `up` levels higher, 3 for `u4a` and 2 for `u4b`.
+    assert_eq!(generate("u4a", 1, 0).unwrap().n, 15);
+    assert_eq!(generate("u4b", 1, 0).unwrap().n, 7);
+}
+
+#[test]
+fn five_components_have_exactly_n_vertices() {
+    for n in (14..200).chain([1000, 12345]) {
+        let g = generate("g3", n, n as u64).unwrap();
+        assert!(g.directed);
+        assert_eq!(g.n, n);
+        check_vertices_in_range(&g);
+        let components = g.to_graph::<()>().find_strongly_connected_components();
+        assert_eq!(components.len(), 5, "{}", n);
+    }
+    for n in [0, 1, 10, 13] {
+        assert_eq!(generate("g3", n, 0).unwrap().n, 14);
+    }
+}
+
+#[test]
+fn same_seed_gives_the_same_graph() {
+    for family in FAMILIES {
+        for n in [10, 100, 1000] {
+            let a = written(&generate(family, n, 7).unwrap());
+            let b = written(&generate(family, n, 7).unwrap());
+            assert_eq!(a, b, "{} {}", family, n);
+        }
+    }
+    // Only the arcs between the components of `g3` depend on the seed.
+    let seeds: Vec<String> = (0..8)
+        .map(|seed| written(&generate("g3", 1000, seed).unwrap()))
+        .collect();
+    assert!(seeds.iter().any(|text| *text != seeds[0]));
+}
+
+#[test]
+fn unknown_families_are_rejected() {
+    assert!(generate("g4", 100, 0).is_none());
+    assert!(generate("", 100, 0).is_none());
+}
+
+#[test]
+fn generated_graphs_match_the_checked_in_instances() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("aod_testy1");
+    for (subdir, family) in [
+        ("2", "g2a"),
+        ("2", "g2b"),
+        ("4", "u4a"),
+        ("4", "u4b"),
+        ("4", "d4a"),
+        ("4", "d4b"),
+    ] {
+        let path = dir.join(subdir).join(format!("{}-1.txt", family));
+        let expected = parse(&fs::read_to_string(&path).unwrap());
+        let generated = parse(&written(&generate(family, expected.1, 0).unwrap()));
+        assert_eq!(generated, expected, "{}", path.display());
+    }
+
+    // `g3` picks the arcs between the components at random, so only the sizes can match.
+    let path = dir.join("3").join("g3-1.txt");
+    let (directed, n, _) = parse(&fs::read_to_string(&path).unwrap());
+    let generated = generate("g3", n, 0).unwrap();
+    assert_eq!((generated.directed, generated.n), (directed, n));
+}
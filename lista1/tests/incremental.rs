@@ -0,0 +1,112 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use lista1::generators::grid_with_diagonals_and_cycle;
+use lista1::incremental::IncrementalTopologicalOrder;
+use lista1::Graph;
+
+fn random_edges(n: usize, m: usize, seed: u64) -> Vec<(usize, usize)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..m)
+        .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+        .collect()
+}
+
+fn build(n: usize, edges: &[(usize, usize)]) -> Graph<()> {
+    let mut graph = Graph::new(n);
+    for &(u, v) in edges {
+        graph.add_edge(u, v);
+    }
+    graph
+}
+
+#[test]
+fn order_stays_topological_and_cycles_are_reported() {
+    for seed in 0..20 {
+        let n = 40;
+        let mut dag = IncrementalTopologicalOrder::new(n);
+        let mut accepted = Vec::new();
+
+        for (u, v) in random_edges(n, 200, seed) {
+            match dag.insert_edge(u, v) {
+                Ok(()) => accepted.push((u, v)),
+                Err(cycle) => {
+                    assert_eq!(cycle[0], u);
+                    assert_eq!(cycle[1 % cycle.len()], v);
+                    for i in 1..cycle.len() {
+                        assert!(accepted.contains(&(cycle[i], cycle[(i + 1) % cycle.len()])));
+                    }
+                    let mut with_edge = accepted.clone();
+                    with_edge.push((u, v));
+                    assert!(build(n, &with_edge).topological_sort().is_none());
+                }
+            }
+
+            let order = dag.order();
+            for &(a, b) in accepted.iter() {
+                assert!(order[a] < order[b], "seed {}: {} -> {}", seed, a, b);
+            }
+        }
+
+        assert!(build(n, &accepted).topological_sort().is_some());
+    }
+}
+
+#[test]
+fn merging_matches_strongly_connected_components() {
+    for seed in 0..20 {
+        let n = 40;
+        let edges = random_edges(n, 60, seed);
+        let mut dag = IncrementalTopologicalOrder::new(n);
+        for &(u, v) in edges.iter() {
+            dag.insert_edge_merging(u, v);
+
+            let order = dag.order();
+            for &(a, b) in edges.iter().take_while(|&&e| e != (u, v)) {
+                if dag.component(a) != dag.component(b) {
+                    assert!(order[a] < order[b], "seed {}: {} -> {}", seed, a, b);
+                }
+            }
+        }
+
+        let mut expected: Vec<Vec<usize>> = build(n, &edges)
+            .find_strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect();
+        expected.sort();
+
+        let mut actual: Vec<Vec<usize>> = (0..n)
+            .filter(|&u| dag.component(u) == u)
+            .map(|u| {
+                let mut c = dag.component_members(u).to_vec();
+                c.sort_unstable();
+                c
+            })
+            .collect();
+        actual.sort();
+
+        assert_eq!(expected, actual, "seed {}", seed);
+    }
+}
+
+#[test]
+fn g2b_cycle_is_detected_on_the_extra_edge() {
+    let graph = grid_with_diagonals_and_cycle(100);
+    let (last, edges) = graph.edges.split_last().unwrap();
+
+    let mut dag = IncrementalTopologicalOrder::new(graph.n);
+    for &(u, v) in edges {
+        assert!(dag.insert_edge(u, v).is_ok());
+    }
+    let cycle = dag.insert_edge(last.0, last.1).unwrap_err();
+    assert_eq!(cycle[..2], [last.0, last.1]);
+    // the back diagonal closes cycles through the whole middle cell
+    assert_eq!(
+        dag.insert_edge_merging(last.0, last.1).map(|c| c.len()),
+        Some(4)
+    );
+}
@@ -0,0 +1,95 @@
+use lista1::Graph;
+
+fn build(n: usize, edges: &[(usize, usize)]) -> Graph<char> {
+    let mut graph = Graph::new(n);
+    for &(u, v) in edges {
+        graph.add_edge(u, v);
+    }
+    for u in 0..n {
+        graph.get_node(u).borrow_mut().data = Some((b'a' + u as u8) as char);
+    }
+    graph
+}
+
+fn edges<T>(graph: &Graph<T>) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for u in 0..graph.get_n() {
+        let node = graph.get_node(u);
+        let node = node.borrow();
+        assert_eq!(node.index, u);
+        for v in node.edges.iter() {
+            edges.push((u, v.borrow().index));
+        }
+    }
+    edges
+}
+
+#[test]
+fn remove_edge_removes_one_copy() {
+    let mut graph = build(3, &[(0, 1), (0, 1), (1, 2)]);
+    assert!(graph.remove_edge(0, 1));
+    assert_eq!(edges(&graph), [(0, 1), (1, 2)]);
+    assert!(graph.remove_edge(0, 1));
+    assert!(!graph.remove_edge(0, 1));
+    assert!(!graph.remove_edge(2, 1));
+    assert_eq!(edges(&graph), [(1, 2)]);
+}
+
+#[test]
+fn remove_node_drops_incident_edges_and_shifts_indices() {
+    let mut graph = build(4, &[(0, 1), (1, 2), (2, 1), (2, 3), (3, 0), (1, 1)]);
+    assert_eq!(graph.remove_node(1), Some('b'));
+    assert_eq!(graph.get_n(), 3);
+    assert_eq!(edges(&graph), [(1, 2), (2, 0)]);
+    assert_eq!(graph.get_node(1).borrow().data, Some('c'));
+
+    assert_eq!(graph.add_node(), 3);
+    graph.add_edge(3, 0);
+    assert_eq!(edges(&graph), [(1, 2), (2, 0), (3, 0)]);
+}
+
+#[test]
+fn induced_subgraph_keeps_edges_inside_the_vertex_set() {
+    let graph = build(5, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)]);
+    let sub = graph.induced_subgraph(&[4, 2, 3]);
+    assert_eq!(sub.get_n(), 3);
+    assert_eq!(edges(&sub), [(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(sub.get_node(0).borrow().data, None);
+    assert_eq!(edges(&graph).len(), 6);
+}
+
+#[test]
+#[should_panic(expected = "vertex 2 appears twice")]
+fn induced_subgraph_rejects_duplicate_vertices() {
+    let graph = build(3, &[(0, 1), (1, 2)]);
+    graph.induced_subgraph(&[2, 0, 2]);
+}
+
+#[test]
+#[should_panic]
+fn induced_subgraph_rejects_vertices_out_of_range() {
+    let graph = build(3, &[(0, 1), (1, 2)]);
+    graph.induced_subgraph(&[0, 3]);
+}
+
+#[test]
+#[should_panic]
+fn remove_node_rejects_indices_out_of_range() {
+    let mut graph = build(3, &[(0, 1), (1, 2)]);
+    graph.remove_node(3);
+}
+
+#[test]
+fn reverse_flips_every_edge_and_keeps_data() {
+    let mut graph = build(4, &[(0, 1), (0, 2), (1, 2), (3, 3), (2, 0)]);
+    graph.reverse();
+    let mut reversed = edges(&graph);
+    reversed.sort();
+    assert_eq!(reversed, [(0, 2), (1, 0), (2, 0), (2, 1), (3, 3)]);
+    assert_eq!(graph.get_node(3).borrow().data, Some('d'));
+
+    graph.reverse();
+    let mut restored = edges(&graph);
+    restored.sort();
+    assert_eq!(restored, [(0, 1), (0, 2), (1, 2), (2, 0), (3, 3)]);
+}
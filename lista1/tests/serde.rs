@@ -0,0 +1,77 @@
+#![cfg(feature = "serde")]
+
+use std::path::Path;
+
+use lista1::{Graph, Node};
+
+fn load(dir: &str, name: &str) -> Graph<()> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("aod_testy1")
+        .join(dir)
+        .join(name);
+    Graph::build_from_file(path.to_str().unwrap()).unwrap()
+}
+
+fn adjacency<T>(graph: &Graph<T>) -> Vec<Vec<usize>> {
+    (0..graph.get_n())
+        .map(|u| {
+            let node = graph.get_node(u);
+            let node = node.borrow();
+            node.edges.iter().map(|v| v.borrow().index).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn graph_round_trips_through_json() {
+    let graph = load("3", "g3-2.txt");
+    let json = serde_json::to_string(&graph).unwrap();
+    let loaded: Graph<()> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(adjacency(&graph), adjacency(&loaded));
+    assert_eq!(
+        graph.find_strongly_connected_components(),
+        loaded.find_strongly_connected_components()
+    );
+}
+
+#[test]
+fn node_data_is_preserved() {
+    let mut graph: Graph<String> = Graph::new(2);
+    graph.add_edge(0, 1);
+    graph.get_node(1).borrow_mut().data = Some("sink".to_string());
+
+    let json = serde_json::to_string(&graph).unwrap();
+    let loaded: Graph<String> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded.get_node(0).borrow().data, None);
+    assert_eq!(loaded.get_node(1).borrow().data.as_deref(), Some("sink"));
+    assert_eq!(adjacency(&loaded), vec![vec![1], vec![]]);
+}
+
+#[test]
+fn analysis_results_round_trip_through_json() {
+    let mut graph = load("2", "g2a-2.txt");
+    let order = graph.topological_sort().unwrap();
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(order, serde_json::from_str::<Vec<usize>>(&json).unwrap());
+
+    let graph = load("4", "u4a-2.txt");
+    let bipartition = graph.get_bipartition().unwrap();
+    let json = serde_json::to_string(&bipartition).unwrap();
+    assert_eq!(
+        bipartition,
+        serde_json::from_str::<(Vec<usize>, Vec<usize>)>(&json).unwrap()
+    );
+
+    let tree = graph.bfs_with_tree(&mut |_: &Node<()>| {});
+    let json = serde_json::to_string(&tree).unwrap();
+    let loaded: Graph<()> = serde_json::from_str(&json).unwrap();
+    assert_eq!(adjacency(&tree), adjacency(&loaded));
+}
+
+#[test]
+fn out_of_range_edges_are_rejected() {
+    let json = r#"[{"data":null,"edges":[1]},{"data":null,"edges":[2]}]"#;
+    assert!(serde_json::from_str::<Graph<()>>(json).is_err());
+}
@@ -0,0 +1,66 @@
+// Graphs shared by the benches. Every bench includes this module but uses only some of the
+// helpers, and the rest would be reported as dead code there.
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, Graph};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use lista3::dimacs::read_gr_file;
+
+// Grid with arcs in both directions between neighbours, like the Square-C (`rows == cols`)
+// and Long-C (16 rows) families of the 9th DIMACS challenge, with costs drawn from 0..=max_cost.
+pub fn grid(rows: usize, cols: usize, max_cost: u64) -> Graph<(), u64, Directed> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut graph = Graph::with_capacity(rows * cols, 4 * rows * cols);
+    for _ in 0..rows * cols {
+        graph.add_node(());
+    }
+    for r in 0..rows {
+        for c in 0..cols {
+            let u = NodeIndex::new(r * cols + c);
+            let mut neighbours = Vec::new();
+            if c + 1 < cols {
+                neighbours.push(NodeIndex::new(r * cols + c + 1));
+            }
+            if r + 1 < rows {
+                neighbours.push(NodeIndex::new((r + 1) * cols + c));
+            }
+            for v in neighbours {
+                graph.add_edge(u, v, rng.gen_range(0..=max_cost));
+                graph.add_edge(v, u, rng.gen_range(0..=max_cost));
+            }
+        }
+    }
+    graph
+}
+
+// Random graph with `4 * n` arcs, like the Random4-C family, on top of a Hamiltonian cycle
+// so that every vertex is reachable, with costs drawn from 0..=max_cost.
+pub fn random4(n: usize, max_cost: u64) -> Graph<(), u64, Directed> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut graph = Graph::with_capacity(n, 4 * n);
+    for _ in 0..n {
+        graph.add_node(());
+    }
+    for v in 0..n {
+        let u = NodeIndex::new(v);
+        graph.add_edge(u, NodeIndex::new((v + 1) % n), rng.gen_range(0..=max_cost));
+        for _ in 0..3 {
+            let w = NodeIndex::new(rng.gen_range(0..n));
+            graph.add_edge(u, w, rng.gen_range(0..=max_cost));
+        }
+    }
+    graph
+}
+
+// An instance of the checked-in data set in tests/data, like `random-200.gr`.
+pub fn checked_in(name: &str) -> Graph<(), u64, Directed> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name);
+    read_gr_file(&path).unwrap().0
+}
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, Graph};
+
+use lista3::algorithms::{delta_stepping_all, dijkstra_all};
+
+mod common;
+
+use common::{grid, random4};
+
+// Dijkstra against delta-stepping with bucket widths spread around the mean arc cost.
+fn bench_graph(c: &mut Criterion, family: &str, graph: &Graph<(), u64, Directed>, i: u32) {
+    let mut group = c.benchmark_group(format!("{}.{}", family, i));
+    group.sample_size(10);
+
+    let start = NodeIndex::new(0);
+    let mean_cost = graph.edge_weights().sum::<u64>() / graph.edge_count() as u64;
+
+    group.bench_function("dijkstra", |b| b.iter(|| dijkstra_all(graph, start)));
+    let mut deltas: Vec<u64> = [mean_cost / 4, mean_cost, mean_cost * 4]
+        .iter()
+        .map(|&delta| delta.max(1))
+        .collect();
+    deltas.dedup();
+    for delta in deltas {
+        group.bench_function(BenchmarkId::new("delta-stepping", delta), |b| {
+            b.iter(|| delta_stepping_all(graph, start, delta))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_delta(c: &mut Criterion) {
+    for i in [0, 3, 6] {
+        let max_cost = 4u64.pow(i);
+        bench_graph(c, "Square-C", &grid(128, 128, max_cost), i);
+        bench_graph(c, "Long-C", &grid(16, 1024, max_cost), i);
+        bench_graph(c, "Random4-C", &random4(16384, max_cost), i);
+    }
+}
+
+criterion_group!(benches, bench_delta);
+criterion_main!(benches);
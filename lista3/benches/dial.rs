@@ -0,0 +1,122 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeRef, NodeIndexable, VisitMap, Visitable};
+use petgraph::{Directed, Graph};
+
+use lista3::algorithms::dial_all;
+
+mod common;
+
+use common::{checked_in, grid};
+
+// The previous implementation, with `Vec` buckets and decrease-key by `retain`.
+fn dial_all_vec_buckets(
+    graph: &Graph<(), u64, Directed>,
+    start_node: NodeIndex,
+    max_cost: usize,
+) -> Vec<Option<usize>> {
+    let mut distance: Vec<_> = vec![None; graph.node_bound()];
+    let mut buckets: Vec<_> = vec![Vec::new(); max_cost + 1];
+    let mut in_bucket: Vec<_> = vec![false; graph.node_bound()];
+
+    let mut visited = graph.visit_map();
+    let start_index = graph.to_index(start_node);
+
+    distance[start_index] = Some(0);
+    buckets[0].push(start_index);
+    in_bucket[start_index] = true;
+
+    let mut i = 0;
+    loop {
+        let start = i;
+        while buckets[i % (max_cost + 1)].is_empty() {
+            i += 1;
+            if i - start > max_cost {
+                return distance;
+            }
+        }
+
+        let node_index = buckets[i % (max_cost + 1)].pop().unwrap();
+
+        let node_id = graph.from_index(node_index);
+        in_bucket[node_index] = false;
+
+        if visited.visit(node_id) {
+            for edge in graph.edges(node_id) {
+                let next_index = graph.to_index(edge.target());
+                let new_cost = i + *edge.weight() as usize;
+
+                if let Some(old_cost) = distance[next_index] {
+                    if new_cost < old_cost {
+                        if in_bucket[next_index] {
+                            buckets[old_cost % (max_cost + 1)].retain(|&x| x != next_index)
+                        }
+                        buckets[new_cost % (max_cost + 1)].push(next_index);
+                        in_bucket[next_index] = true;
+                        distance[next_index] = Some(new_cost);
+                    }
+                } else {
+                    buckets[new_cost % (max_cost + 1)].push(next_index);
+                    in_bucket[next_index] = true;
+                    distance[next_index] = Some(new_cost);
+                }
+            }
+        }
+    }
+}
+
+fn bench_family(c: &mut Criterion, family: &str, rows: usize, cols: usize) {
+    let mut group = c.benchmark_group(family);
+    group.sample_size(10);
+
+    for i in [0, 3, 6, 9] {
+        let max_cost = 4u64.pow(i);
+        let graph = grid(rows, cols, max_cost);
+        let max_cost = *graph.edge_weights().max().unwrap() as usize;
+        let start = NodeIndex::new(0);
+
+        group.bench_function(BenchmarkId::new("linked buckets", i), |b| {
+            b.iter(|| dial_all(&graph, start, max_cost))
+        });
+        group.bench_function(BenchmarkId::new("vec buckets", i), |b| {
+            b.iter(|| dial_all_vec_buckets(&graph, start, max_cost))
+        });
+    }
+
+    group.finish();
+}
+
+// Searches from every vertex of a checked-in instance, which is too small for one search to
+// take measurable time.
+fn bench_checked_in(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+
+    let graph = checked_in(name);
+    let max_cost = *graph.edge_weights().max().unwrap() as usize;
+    group.bench_function("linked buckets", |b| {
+        b.iter(|| {
+            for start in graph.node_indices() {
+                dial_all(&graph, start, max_cost);
+            }
+        })
+    });
+    group.bench_function("vec buckets", |b| {
+        b.iter(|| {
+            for start in graph.node_indices() {
+                dial_all_vec_buckets(&graph, start, max_cost);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_dial(c: &mut Criterion) {
+    bench_family(c, "Square-C", 128, 128);
+    bench_family(c, "Long-C", 16, 1024);
+    bench_checked_in(c, "random-200.gr");
+}
+
+criterion_group!(benches, bench_dial);
+criterion_main!(benches);
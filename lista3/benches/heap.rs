@@ -0,0 +1,52 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, Graph};
+
+use lista3::algorithms::dijkstra_all_with;
+use lista3::heap::{DaryHeap, FibonacciHeap, LazyBinaryHeap, PairingHeap, RadixHeap};
+
+mod common;
+
+use common::{grid, random4};
+
+fn bench_graph(c: &mut Criterion, family: &str, graph: &Graph<(), u64, Directed>, i: u32) {
+    let mut group = c.benchmark_group(format!("{}.{}", family, i));
+    group.sample_size(10);
+
+    let start = NodeIndex::new(0);
+    group.bench_function("binary", |b| {
+        b.iter(|| dijkstra_all_with::<LazyBinaryHeap, _>(graph, start))
+    });
+    group.bench_function("2-ary", |b| {
+        b.iter(|| dijkstra_all_with::<DaryHeap<2>, _>(graph, start))
+    });
+    group.bench_function("4-ary", |b| {
+        b.iter(|| dijkstra_all_with::<DaryHeap<4>, _>(graph, start))
+    });
+    group.bench_function("8-ary", |b| {
+        b.iter(|| dijkstra_all_with::<DaryHeap<8>, _>(graph, start))
+    });
+    group.bench_function("pairing", |b| {
+        b.iter(|| dijkstra_all_with::<PairingHeap, _>(graph, start))
+    });
+    group.bench_function("fibonacci", |b| {
+        b.iter(|| dijkstra_all_with::<FibonacciHeap, _>(graph, start))
+    });
+    group.bench_function("radix", |b| {
+        b.iter(|| dijkstra_all_with::<RadixHeap, _>(graph, start))
+    });
+
+    group.finish();
+}
+
+fn bench_heaps(c: &mut Criterion) {
+    for i in [0, 3, 6] {
+        let max_cost = 4u64.pow(i);
+        bench_graph(c, "Square-C", &grid(128, 128, max_cost), i);
+        bench_graph(c, "Long-C", &grid(16, 1024, max_cost), i);
+        bench_graph(c, "Random4-C", &random4(16384, max_cost), i);
+    }
+}
+
+criterion_group!(benches, bench_heaps);
+criterion_main!(benches);
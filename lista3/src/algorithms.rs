@@ -1,75 +1,288 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
-use petgraph::graph::NodeIndex;
+use petgraph::graph::{EdgeIndex, NodeIndex};
 use petgraph::visit::{EdgeRef, NodeIndexable, VisitMap, Visitable};
-use petgraph::{Graph, Undirected};
+use petgraph::{EdgeType, Graph, Incoming};
+use rayon::prelude::*;
 
-pub fn dijkstra_all(graph: &Graph<(), u64, Undirected>, start: NodeIndex) -> Vec<u64> {
-    let mut scores = vec![u64::MAX; graph.node_bound()];
-    let mut visit_next = BinaryHeap::new();
+use crate::heap::{LazyBinaryHeap, MonotonePriorityQueue};
+
+/// Distances from a single source together with the arc used to reach every vertex.
+///
+/// Unreachable vertices have distance `u64::MAX` and no predecessor.
+pub struct ShortestPathTree {
+    pub distances: Vec<u64>,
+    pub predecessors: Vec<Option<(NodeIndex, EdgeIndex)>>,
+    /// Number of vertices settled (removed from the queue for good) by the search.
+    pub settled: usize,
+}
+
+pub struct Route {
+    pub nodes: Vec<NodeIndex>,
+    pub edges: Vec<EdgeIndex>,
+}
+
+impl ShortestPathTree {
+    pub(crate) fn new(node_bound: usize, start: NodeIndex) -> ShortestPathTree {
+        let mut distances = vec![u64::MAX; node_bound];
+        distances[start.index()] = 0;
+        ShortestPathTree {
+            distances,
+            predecessors: vec![None; node_bound],
+            settled: 0,
+        }
+    }
+
+    pub(crate) fn relax(
+        &mut self,
+        node: NodeIndex,
+        edge: EdgeIndex,
+        next: NodeIndex,
+        next_score: u64,
+    ) {
+        self.distances[next.index()] = next_score;
+        self.predecessors[next.index()] = Some((node, edge));
+    }
+
+    /// Returns the vertices and arcs on the tree path from the source to `target`.
+    pub fn path(&self, target: NodeIndex) -> Option<Route> {
+        if self.distances[target.index()] == u64::MAX {
+            return None;
+        }
+
+        let mut nodes = vec![target];
+        let mut edges = Vec::new();
+        let mut current = target;
+        while let Some((previous, edge)) = self.predecessors[current.index()] {
+            nodes.push(previous);
+            edges.push(edge);
+            current = previous;
+        }
+        nodes.reverse();
+        edges.reverse();
+
+        Some(Route { nodes, edges })
+    }
+
+    /// Sum of the finite distances modulo 2^62, the per-source checksum of the 9th DIMACS
+    /// challenge.
+    pub fn checksum(&self) -> u64 {
+        let sum = self
+            .distances
+            .iter()
+            .filter(|&&distance| distance != u64::MAX)
+            .fold(0u64, |sum, &distance| sum.wrapping_add(distance));
+        sum & CHECKSUM_MASK
+    }
+}
+
+/// Distance checksums are kept modulo 2^62, that is masked with this.
+pub const CHECKSUM_MASK: u64 = (1 << 62) - 1;
+
+pub fn dijkstra_all<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+) -> ShortestPathTree {
+    dijkstra_all_with::<LazyBinaryHeap, Ty>(graph, start)
+}
+
+pub fn dijkstra_single<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    end: NodeIndex,
+) -> ShortestPathTree {
+    dijkstra_single_with::<LazyBinaryHeap, Ty>(graph, start, end)
+}
+
+/// [`dijkstra_all`] with the priority queue `Q` in place of the lazy binary heap.
+pub fn dijkstra_all_with<Q: MonotonePriorityQueue, Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+) -> ShortestPathTree {
+    dijkstra_with::<Q, Ty>(graph, start, None)
+}
+
+/// [`dijkstra_single`] with the priority queue `Q` in place of the lazy binary heap.
+pub fn dijkstra_single_with<Q: MonotonePriorityQueue, Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    end: NodeIndex,
+) -> ShortestPathTree {
+    dijkstra_with::<Q, Ty>(graph, start, Some(end))
+}
+
+fn dijkstra_with<Q: MonotonePriorityQueue, Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    end: Option<NodeIndex>,
+) -> ShortestPathTree {
+    let mut tree = ShortestPathTree::new(graph.node_bound(), start);
+    let mut visit_next = Q::new(graph.node_bound());
     let mut visited = graph.visit_map();
     let start_score = 0;
-    scores[start.index()] = start_score;
-    visit_next.push(NoOrd(start_score, start));
+    visit_next.push(start.index(), start_score);
 
-    while let Some(NoOrd(_, node)) = visit_next.pop() {
+    while let Some((_, node)) = visit_next.pop() {
+        let node = NodeIndex::new(node);
         if !visited.visit(node) {
             continue;
         }
+        tree.settled += 1;
+        if Some(node) == end {
+            break;
+        }
         for edge in graph.edges(node) {
             let next = edge.target();
             if visited.is_visited(&next) {
                 continue;
             }
-            let next_score = scores[node.index()] + edge.weight();
-            let old_next_score = scores[next.index()];
+            let next_score = tree.distances[node.index()] + edge.weight();
+            let old_next_score = tree.distances[next.index()];
             if next_score < old_next_score {
-                scores[next.index()] = next_score;
-                visit_next.push(NoOrd(next_score, next));
+                tree.relax(node, edge.id(), next, next_score);
+                visit_next.push(next.index(), next_score);
             }
         }
     }
-    scores
+    tree
 }
 
-pub fn dijkstra_single(
-    graph: &Graph<(), u64, Undirected>,
+/// Dijkstra's algorithm run from `start` over outgoing arcs and from `end` over incoming
+/// arcs at once, always advancing the search with the smaller queue minimum.
+///
+/// Distances and predecessors are those of the forward search, with the backward half
+/// of the shortest route relinked onto it, so `path(end)` gives the route. `settled`
+/// counts the vertices settled by both searches.
+pub fn bidirectional_dijkstra_single<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
     start: NodeIndex,
     end: NodeIndex,
-) -> u64 {
-    let mut scores = vec![u64::MAX; graph.node_bound()];
-    let mut visit_next = BinaryHeap::new();
-    let mut visited = graph.visit_map();
-    let start_score = 0;
-    scores[start.index()] = start_score;
-    visit_next.push(NoOrd(start_score, start));
+) -> ShortestPathTree {
+    let mut forward = ShortestPathTree::new(graph.node_bound(), start);
+    let mut backward = ShortestPathTree::new(graph.node_bound(), end);
+    let mut forward_next = BinaryHeap::new();
+    let mut backward_next = BinaryHeap::new();
+    let mut forward_visited = graph.visit_map();
+    let mut backward_visited = graph.visit_map();
+    forward_next.push(NoOrd(0, start));
+    backward_next.push(NoOrd(0, end));
 
-    while let Some(NoOrd(_, node)) = visit_next.pop() {
-        if !visited.visit(node) {
-            continue;
+    // Length of the shortest path seen so far and the arc joining its two halves.
+    let mut best = if start == end { 0 } else { u64::MAX };
+    let mut meeting = None;
+
+    loop {
+        while let Some(&NoOrd(_, node)) = forward_next.peek() {
+            if !forward_visited.is_visited(&node) {
+                break;
+            }
+            forward_next.pop();
         }
-        for edge in graph.edges(node) {
-            let next = edge.target();
-            if visited.is_visited(&next) {
-                continue;
+        while let Some(&NoOrd(_, node)) = backward_next.peek() {
+            if !backward_visited.is_visited(&node) {
+                break;
             }
-            let next_score = scores[node.index()] + edge.weight();
-            let old_next_score = scores[next.index()];
-            if next_score < old_next_score {
-                scores[next.index()] = next_score;
-                visit_next.push(NoOrd(next_score, next));
+            backward_next.pop();
+        }
+
+        let (forward_top, backward_top): (u64, u64) =
+            match (forward_next.peek(), backward_next.peek()) {
+                (Some(&NoOrd(forward_top, _)), Some(&NoOrd(backward_top, _))) => {
+                    (forward_top, backward_top)
+                }
+                _ => break,
+            };
+        // Any path not seen yet leaves both settled regions, so it is at least this long.
+        if forward_top.saturating_add(backward_top) >= best {
+            break;
+        }
+
+        if forward_top <= backward_top {
+            let NoOrd(_, node) = forward_next.pop().unwrap();
+            forward_visited.visit(node);
+            forward.settled += 1;
+            for edge in graph.edges(node) {
+                let next = edge.target();
+                let next_score = forward.distances[node.index()] + edge.weight();
+                let remaining = backward.distances[next.index()];
+                if remaining != u64::MAX && next_score + remaining < best {
+                    best = next_score + remaining;
+                    meeting = Some((node, edge.id(), next));
+                }
+                if forward_visited.is_visited(&next) {
+                    continue;
+                }
+                if next_score < forward.distances[next.index()] {
+                    forward.relax(node, edge.id(), next, next_score);
+                    forward_next.push(NoOrd(next_score, next));
+                }
+            }
+        } else {
+            let NoOrd(_, node) = backward_next.pop().unwrap();
+            backward_visited.visit(node);
+            backward.settled += 1;
+            for edge in graph.edges_directed(node, Incoming) {
+                let next = edge.source();
+                let next_score = backward.distances[node.index()] + edge.weight();
+                let remaining = forward.distances[next.index()];
+                if remaining != u64::MAX && next_score + remaining < best {
+                    best = next_score + remaining;
+                    meeting = Some((next, edge.id(), node));
+                }
+                if backward_visited.is_visited(&next) {
+                    continue;
+                }
+                if next_score < backward.distances[next.index()] {
+                    backward.relax(node, edge.id(), next, next_score);
+                    backward_next.push(NoOrd(next_score, next));
+                }
             }
-            if next == end {
-                return next_score;
+        }
+    }
+
+    if let Some((node, edge, next)) = meeting {
+        let mut on_forward_route = vec![false; graph.node_bound()];
+        let mut current = node;
+        on_forward_route[current.index()] = true;
+        while let Some((previous, _)) = forward.predecessors[current.index()] {
+            on_forward_route[previous.index()] = true;
+            current = previous;
+        }
+
+        let mut backward_route = vec![next];
+        let mut current = next;
+        while let Some((following, _)) = backward.predecessors[current.index()] {
+            backward_route.push(following);
+            current = following;
+        }
+
+        // With zero-cost cycles the backward half may run into the forward one; joining
+        // at the last common vertex keeps the route simple.
+        let mut current = match backward_route
+            .iter()
+            .rposition(|node| on_forward_route[node.index()])
+        {
+            Some(i) => backward_route[i],
+            None => {
+                let score = forward.distances[node.index()] + graph[edge];
+                forward.relax(node, edge, next, score);
+                next
             }
+        };
+        while let Some((following, edge)) = backward.predecessors[current.index()] {
+            let score = forward.distances[current.index()] + graph[edge];
+            forward.relax(current, edge, following, score);
+            current = following;
         }
     }
-    scores[end.index()]
+
+    forward.settled += backward.settled;
+    forward
 }
 
 #[derive(Copy, Clone, Debug)]
-struct NoOrd<K, T>(K, T);
+pub(crate) struct NoOrd<K, T>(pub(crate) K, pub(crate) T);
 
 impl<K: PartialEq, T> PartialEq for NoOrd<K, T> {
     fn eq(&self, other: &NoOrd<K, T>) -> bool {
@@ -81,7 +294,7 @@ impl<K: PartialEq, T> Eq for NoOrd<K, T> {}
 
 impl<K: PartialOrd, T> PartialOrd for NoOrd<K, T> {
     fn partial_cmp(&self, other: &NoOrd<K, T>) -> Option<Ordering> {
-        other.0.partial_cmp(&self.0)
+        Some(self.cmp(other))
     }
 }
 
@@ -91,118 +304,327 @@ impl<K: PartialOrd, T> Ord for NoOrd<K, T> {
     }
 }
 
-pub fn dial_all(
-    graph: &Graph<(), u64, Undirected>,
+const NO_NODE: usize = usize::MAX;
+
+// Dial's buckets as intrusive doubly-linked lists over node indices, so that a node
+// can be moved to another bucket in O(1). A node is in at most one bucket at a time.
+struct Buckets {
+    heads: Vec<usize>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl Buckets {
+    fn new(count: usize, node_bound: usize) -> Buckets {
+        Buckets {
+            heads: vec![NO_NODE; count],
+            next: vec![NO_NODE; node_bound],
+            prev: vec![NO_NODE; node_bound],
+        }
+    }
+
+    fn is_empty(&self, bucket: usize) -> bool {
+        self.heads[bucket] == NO_NODE
+    }
+
+    fn push(&mut self, bucket: usize, node: usize) {
+        let head = self.heads[bucket];
+        self.next[node] = head;
+        self.prev[node] = NO_NODE;
+        if head != NO_NODE {
+            self.prev[head] = node;
+        }
+        self.heads[bucket] = node;
+    }
+
+    fn remove(&mut self, bucket: usize, node: usize) {
+        let (prev, next) = (self.prev[node], self.next[node]);
+        if prev == NO_NODE {
+            self.heads[bucket] = next;
+        } else {
+            self.next[prev] = next;
+        }
+        if next != NO_NODE {
+            self.prev[next] = prev;
+        }
+    }
+
+    fn pop(&mut self, bucket: usize) -> Option<usize> {
+        let node = self.heads[bucket];
+        if node == NO_NODE {
+            return None;
+        }
+        self.remove(bucket, node);
+        Some(node)
+    }
+}
+
+pub fn dial_all<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
     start_node: NodeIndex,
     max_cost: usize,
-) -> Vec<Option<usize>> {
-    let mut distance: Vec<_> = vec![None; graph.node_bound()];
-    let mut buckets: Vec<_> = vec![Vec::new(); max_cost + 1];
+) -> ShortestPathTree {
+    let mut tree = ShortestPathTree::new(graph.node_bound(), start_node);
+    let mut buckets = Buckets::new(max_cost + 1, graph.node_bound());
     let mut in_bucket: Vec<_> = vec![false; graph.node_bound()];
 
     let mut visited = graph.visit_map();
     let start_index = graph.to_index(start_node);
 
-    distance[start_index] = Some(0);
-    buckets[0].push(start_index);
+    buckets.push(0, start_index);
     in_bucket[start_index] = true;
 
     let mut i = 0;
     loop {
         let start = i;
-        while buckets[i % (max_cost + 1)].is_empty() {
+        while buckets.is_empty(i % (max_cost + 1)) {
             i += 1;
             if i - start > max_cost {
-                return distance;
+                return tree;
             }
         }
 
-        let node_index = buckets[i % (max_cost + 1)].pop().unwrap();
+        let node_index = buckets.pop(i % (max_cost + 1)).unwrap();
 
         let node_id = graph.from_index(node_index);
         in_bucket[node_index] = false;
 
         if visited.visit(node_id) {
+            tree.settled += 1;
             for edge in graph.edges(node_id) {
-                let next_index = graph.to_index(edge.target());
+                let next_id = edge.target();
+                let next_index = graph.to_index(next_id);
                 let new_cost = i + *edge.weight() as usize;
+                let old_cost = tree.distances[next_index];
 
-                if let Some(old_cost) = distance[next_index] {
-                    if new_cost < old_cost {
-                        if in_bucket[next_index] {
-                            buckets[old_cost % (max_cost + 1)].retain(|&x| x != next_index)
-                        }
-                        buckets[new_cost % (max_cost + 1)].push(next_index);
-                        in_bucket[next_index] = true;
-                        distance[next_index] = Some(new_cost);
+                if old_cost == u64::MAX {
+                    buckets.push(new_cost % (max_cost + 1), next_index);
+                    in_bucket[next_index] = true;
+                    tree.relax(node_id, edge.id(), next_id, new_cost as u64);
+                } else if new_cost < old_cost as usize {
+                    if in_bucket[next_index] {
+                        buckets.remove(old_cost as usize % (max_cost + 1), next_index);
                     }
-                } else {
-                    buckets[new_cost % (max_cost + 1)].push(next_index);
+                    buckets.push(new_cost % (max_cost + 1), next_index);
                     in_bucket[next_index] = true;
-                    distance[next_index] = Some(new_cost);
+                    tree.relax(node_id, edge.id(), next_id, new_cost as u64);
                 }
             }
         }
     }
 }
 
-pub fn dial_single(
-    graph: &Graph<(), u64, Undirected>,
+pub fn dial_single<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
     start_node: NodeIndex,
     end_node: NodeIndex,
     max_cost: usize,
-) -> Option<usize> {
-    let mut distance: Vec<_> = vec![None; graph.node_bound()];
-    let mut buckets: Vec<_> = vec![Vec::new(); max_cost + 1];
+) -> ShortestPathTree {
+    let mut tree = ShortestPathTree::new(graph.node_bound(), start_node);
+    let mut buckets = Buckets::new(max_cost + 1, graph.node_bound());
     let mut in_bucket: Vec<_> = vec![false; graph.node_bound()];
 
     let mut visited = graph.visit_map();
     let start_index = graph.to_index(start_node);
 
-    distance[start_index] = Some(0);
-    buckets[0].push(start_index);
+    buckets.push(0, start_index);
     in_bucket[start_index] = true;
 
     let mut i = 0;
     loop {
         let start = i;
-        while buckets[i % (max_cost + 1)].is_empty() {
+        while buckets.is_empty(i % (max_cost + 1)) {
             i += 1;
             if i - start > max_cost {
-                return distance[graph.to_index(end_node)];
+                return tree;
             }
         }
 
-        let node_index = buckets[i % (max_cost + 1)].pop().unwrap();
+        let node_index = buckets.pop(i % (max_cost + 1)).unwrap();
 
         let node_id = graph.from_index(node_index);
         in_bucket[node_index] = false;
 
         if visited.visit(node_id) {
+            tree.settled += 1;
+            if node_id == end_node {
+                return tree;
+            }
             for edge in graph.edges(node_id) {
-                let next_index = graph.to_index(edge.target());
-                let next_id = graph.from_index(next_index);
+                let next_id = edge.target();
+                let next_index = graph.to_index(next_id);
                 let new_cost = i + *edge.weight() as usize;
+                let old_cost = tree.distances[next_index];
 
-                if let Some(old_cost) = distance[next_index] {
-                    if new_cost < old_cost {
-                        if in_bucket[next_index] {
-                            buckets[old_cost % (max_cost + 1)].retain(|&x| x != next_index);
-                        }
-                        buckets[new_cost % (max_cost + 1)].push(next_index);
-                        in_bucket[next_index] = true;
-                        distance[next_index] = Some(new_cost);
+                if old_cost == u64::MAX {
+                    buckets.push(new_cost % (max_cost + 1), next_index);
+                    in_bucket[next_index] = true;
+                    tree.relax(node_id, edge.id(), next_id, new_cost as u64);
+                } else if new_cost < old_cost as usize {
+                    if in_bucket[next_index] {
+                        buckets.remove(old_cost as usize % (max_cost + 1), next_index);
                     }
-                } else {
-                    buckets[new_cost % (max_cost + 1)].push(next_index);
+                    buckets.push(new_cost % (max_cost + 1), next_index);
                     in_bucket[next_index] = true;
-                    distance[next_index] = Some(new_cost);
+                    tree.relax(node_id, edge.id(), next_id, new_cost as u64);
                 }
+            }
+        }
+    }
+}
 
-                if next_id == end_node {
-                    return distance[next_index];
-                }
+type SplitArc = (NodeIndex, u64, EdgeIndex);
+
+// Outgoing arcs of every vertex as (head, cost, arc) in one array, the light ones (cost at
+// most `delta`) of vertex `v` in `arcs[first[v]..split[v]]` and the heavy ones after them.
+struct SplitArcs {
+    first: Vec<usize>,
+    split: Vec<usize>,
+    arcs: Vec<SplitArc>,
+}
+
+impl SplitArcs {
+    fn new<Ty: EdgeType>(graph: &Graph<(), u64, Ty>, delta: u64) -> SplitArcs {
+        let mut first = Vec::with_capacity(graph.node_bound() + 1);
+        let mut split = Vec::with_capacity(graph.node_bound());
+        let mut arcs = Vec::with_capacity(graph.edge_count());
+        for node in graph.node_indices() {
+            first.push(arcs.len());
+            let edges = || graph.edges(node).map(|e| (e.target(), *e.weight(), e.id()));
+            arcs.extend(edges().filter(|&(_, cost, _)| cost <= delta));
+            split.push(arcs.len());
+            arcs.extend(edges().filter(|&(_, cost, _)| cost > delta));
+        }
+        first.push(arcs.len());
+        SplitArcs { first, split, arcs }
+    }
+
+    fn light(&self, node: usize) -> &[SplitArc] {
+        &self.arcs[self.first[node]..self.split[node]]
+    }
+
+    fn heavy(&self, node: usize) -> &[SplitArc] {
+        &self.arcs[self.split[node]..self.first[node + 1]]
+    }
+}
+
+// Vertices are handed to rayon in chunks of at least this many, so that the small
+// frontiers of most phases are not split between threads.
+const DELTA_STEPPING_GRAIN: usize = 64;
+
+// Improving relaxations of the selected arcs of `nodes`, generated in parallel against the
+// current distances.
+fn relaxation_requests(
+    tree: &ShortestPathTree,
+    arcs: &SplitArcs,
+    nodes: &[usize],
+    select: fn(&SplitArcs, usize) -> &[SplitArc],
+) -> Vec<(NodeIndex, EdgeIndex, NodeIndex, u64)> {
+    nodes
+        .par_iter()
+        .with_min_len(DELTA_STEPPING_GRAIN)
+        .flat_map_iter(|&node| {
+            let score = tree.distances[node];
+            select(arcs, node)
+                .iter()
+                .filter_map(move |&(next, cost, edge)| {
+                    let next_score = score + cost;
+                    (next_score < tree.distances[next.index()])
+                        .then_some((NodeIndex::new(node), edge, next, next_score))
+                })
+        })
+        .collect()
+}
+
+// Applies the requests that still improve a distance, moving their heads into the bucket of
+// the new distance, and returns how many were applied. The requests are sorted by head, so
+// the best one for every head is found in parallel and written by the thread owning that
+// part of the distances; only the bucket pushes are left sequential.
+fn apply_requests(
+    tree: &mut ShortestPathTree,
+    buckets: &mut [Vec<usize>],
+    mut requests: Vec<(NodeIndex, EdgeIndex, NodeIndex, u64)>,
+    delta: u64,
+) -> usize {
+    requests.par_sort_unstable_by_key(|&(_, _, next, next_score)| (next, next_score));
+    let best: Vec<_> = requests
+        .par_iter()
+        .enumerate()
+        .filter(|&(i, &(_, _, next, next_score))| {
+            (i == 0 || requests[i - 1].2 != next) && next_score < tree.distances[next.index()]
+        })
+        .map(|(_, &request)| request)
+        .collect();
+
+    let chunk = DELTA_STEPPING_GRAIN.max(tree.distances.len() / rayon::current_num_threads() + 1);
+    tree.distances
+        .par_chunks_mut(chunk)
+        .zip(tree.predecessors.par_chunks_mut(chunk))
+        .enumerate()
+        .for_each(|(c, (distances, predecessors))| {
+            let first = best.partition_point(|request| request.2.index() < c * chunk);
+            let last = best.partition_point(|request| request.2.index() < (c + 1) * chunk);
+            for &(node, edge, next, next_score) in &best[first..last] {
+                distances[next.index() - c * chunk] = next_score;
+                predecessors[next.index() - c * chunk] = Some((node, edge));
             }
+        });
+
+    for &(_, _, next, next_score) in &best {
+        let bucket = (next_score / delta) % buckets.len() as u64;
+        buckets[bucket as usize].push(next.index());
+    }
+    best.len()
+}
+
+/// Meyer and Sanders' delta-stepping: vertices wait in buckets of width `delta` and the
+/// lowest non-empty bucket is emptied in phases. A phase relaxes the light arcs (cost at
+/// most `delta`) of every vertex in the bucket, which may refill it; once it stays empty,
+/// the heavy arcs of everything it held are relaxed once. The arcs of a phase are scanned
+/// and the resulting relaxations applied in parallel with rayon.
+///
+/// `delta` must be positive. A `delta` of 1 settles vertices in the order of Dial's
+/// algorithm, while one above the largest cost turns it into a parallel Bellman–Ford.
+pub fn delta_stepping_all<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    delta: u64,
+) -> ShortestPathTree {
+    assert!(delta > 0, "delta-stepping needs a positive bucket width");
+    let arcs = SplitArcs::new(graph, delta);
+    let max_cost = graph.edge_weights().copied().max().unwrap_or(0);
+
+    let mut tree = ShortestPathTree::new(graph.node_bound(), start);
+    // Tentative distances exceed the lowest bucket by at most `max_cost`, so the buckets
+    // are reused cyclically like in Dial's algorithm.
+    let mut buckets = vec![Vec::new(); (max_cost / delta) as usize + 2];
+    buckets[0].push(start.index());
+    // Entries left in the buckets, counting those of vertices that moved to a lower one.
+    let mut queued = 1;
+
+    let mut i = 0;
+    while queued > 0 {
+        let slot = (i % buckets.len() as u64) as usize;
+        let mut emptied = Vec::new();
+        while !buckets[slot].is_empty() {
+            let mut frontier = std::mem::take(&mut buckets[slot]);
+            queued -= frontier.len();
+            frontier.retain(|&node| tree.distances[node] / delta == i);
+            frontier.par_sort_unstable();
+            frontier.dedup();
+
+            let requests = relaxation_requests(&tree, &arcs, &frontier, SplitArcs::light);
+            queued += apply_requests(&mut tree, &mut buckets, requests, delta);
+            emptied.extend_from_slice(&frontier);
         }
+
+        // Distances in the bucket are final now, so heavy arcs only need one pass.
+        emptied.par_sort_unstable();
+        emptied.dedup();
+        tree.settled += emptied.len();
+        let requests = relaxation_requests(&tree, &arcs, &emptied, SplitArcs::heavy);
+        queued += apply_requests(&mut tree, &mut buckets, requests, delta);
+        i += 1;
     }
+    tree
 }
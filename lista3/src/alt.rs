@@ -0,0 +1,297 @@
+use std::io::{self, Read, Seek, Write};
+
+use petgraph::graph::NodeIndex;
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::dijkstra_all;
+use crate::astar::Potential;
+use crate::binary::{invalid_data, read_u32, read_u64, remaining_len, write_u32, write_u64};
+
+const MAGIC: &[u8; 8] = b"LISTA3LM";
+
+/// How [`Landmarks::select`] picks the next landmark.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// The vertex farthest from the landmarks chosen so far.
+    Farthest,
+    /// Goldberg and Werneck's avoid: grow a shortest path tree from a vertex far from
+    /// the chosen landmarks and walk down into the subtree they cover worst.
+    Avoid,
+}
+
+/// Distances between every vertex and a few landmarks, giving lower bounds on all
+/// distances through the triangle inequality.
+pub struct Landmarks {
+    pub landmarks: Vec<NodeIndex>,
+    directed: bool,
+    node_count: usize,
+    edge_count: usize,
+    // `from[i][v]` is the distance from landmark `i` to `v` and `to[i][v]` the one back;
+    // undirected graphs only keep `from`.
+    from: Vec<Vec<u64>>,
+    to: Vec<Vec<u64>>,
+}
+
+impl Landmarks {
+    /// Chooses `count` landmarks (at most one per vertex) and computes their distances
+    /// with [`dijkstra_all`], on the reversed graph for the distances towards them.
+    pub fn select<Ty: EdgeType>(
+        graph: &Graph<(), u64, Ty>,
+        count: usize,
+        selection: Selection,
+    ) -> Landmarks {
+        let reversed = graph.is_directed().then(|| {
+            let mut reversed = graph.clone();
+            reversed.reverse();
+            reversed
+        });
+
+        let mut landmarks = Landmarks {
+            landmarks: Vec::new(),
+            directed: graph.is_directed(),
+            node_count: graph.node_count(),
+            edge_count: graph.edge_count(),
+            from: Vec::new(),
+            to: Vec::new(),
+        };
+
+        while landmarks.landmarks.len() < count.min(graph.node_count()) {
+            let landmark = match selection {
+                Selection::Farthest => landmarks.farthest(graph),
+                Selection::Avoid => landmarks.avoid(graph),
+            };
+            landmarks.landmarks.push(landmark);
+            landmarks.from.push(dijkstra_all(graph, landmark).distances);
+            if let Some(reversed) = &reversed {
+                landmarks
+                    .to
+                    .push(dijkstra_all(reversed, landmark).distances);
+            }
+        }
+        landmarks
+    }
+
+    fn to(&self, i: usize) -> &[u64] {
+        if self.directed {
+            &self.to[i]
+        } else {
+            &self.from[i]
+        }
+    }
+
+    // Vertex with the largest distance to its nearest landmark, counting both directions
+    // in directed graphs; vertices no landmark reaches come first. Before the first
+    // landmark is chosen, distances are measured from vertex 0.
+    fn farthest<Ty: EdgeType>(&self, graph: &Graph<(), u64, Ty>) -> NodeIndex {
+        let keys: Vec<u64> = if self.landmarks.is_empty() {
+            dijkstra_all(graph, NodeIndex::new(0)).distances
+        } else {
+            (0..graph.node_count())
+                .map(|v| {
+                    (0..self.landmarks.len())
+                        .map(|i| {
+                            if self.directed {
+                                self.from[i][v].saturating_add(self.to[i][v])
+                            } else {
+                                self.from[i][v]
+                            }
+                        })
+                        .min()
+                        .unwrap()
+                })
+                .collect()
+        };
+
+        let mut best = None;
+        for (v, &key) in keys.iter().enumerate() {
+            let node = NodeIndex::new(v);
+            if self.landmarks.contains(&node) {
+                continue;
+            }
+            if best.is_none_or(|(_, best_key)| key > best_key) {
+                best = Some((node, key));
+            }
+        }
+        best.unwrap().0
+    }
+
+    fn avoid<Ty: EdgeType>(&self, graph: &Graph<(), u64, Ty>) -> NodeIndex {
+        let root = self.farthest(graph);
+        let tree = dijkstra_all(graph, root);
+        let n = graph.node_count();
+
+        let mut children = vec![Vec::new(); n];
+        for v in 0..n {
+            if let Some((parent, _)) = tree.predecessors[v] {
+                children[parent.index()].push(v);
+            }
+        }
+        // Tree vertices with every parent before its children.
+        let mut order = vec![root.index()];
+        let mut i = 0;
+        while i < order.len() {
+            order.extend_from_slice(&children[order[i]]);
+            i += 1;
+        }
+
+        // How much the landmarks underestimate the distance from the root, summed over
+        // each subtree, or zero for subtrees that already hold a landmark.
+        let mut size = vec![0u64; n];
+        let mut has_landmark = vec![false; n];
+        for landmark in self.landmarks.iter() {
+            has_landmark[landmark.index()] = true;
+        }
+        for &v in order.iter().rev() {
+            let weight = tree.distances[v] - self.lower_bound(root.index(), v);
+            size[v] = size[v].saturating_add(weight);
+            if let Some((parent, _)) = tree.predecessors[v] {
+                if has_landmark[v] {
+                    has_landmark[parent.index()] = true;
+                }
+                size[parent.index()] = size[parent.index()].saturating_add(size[v]);
+            }
+        }
+
+        let mut current = root.index();
+        while let Some(&next) = children[current]
+            .iter()
+            .filter(|&&child| !has_landmark[child])
+            .max_by_key(|&&child| size[child])
+        {
+            current = next;
+        }
+        NodeIndex::new(current)
+    }
+
+    // Largest bound given by any landmark, skipping those that cannot reach (or be
+    // reached from) both vertices.
+    fn lower_bound(&self, node: usize, target: usize) -> u64 {
+        let mut bound = 0;
+        for i in 0..self.landmarks.len() {
+            let (from_node, from_target) = (self.from[i][node], self.from[i][target]);
+            if from_node != u64::MAX && from_target != u64::MAX {
+                bound = bound.max(from_target.saturating_sub(from_node));
+            }
+            let to = self.to(i);
+            let (to_node, to_target) = (to[node], to[target]);
+            if to_node != u64::MAX && to_target != u64::MAX {
+                bound = bound.max(to_node.saturating_sub(to_target));
+            }
+        }
+        bound
+    }
+
+    /// Checks that the landmarks were computed for a graph of this shape.
+    pub fn validate<Ty: EdgeType>(&self, graph: &Graph<(), u64, Ty>) -> Result<(), String> {
+        if self.directed != graph.is_directed() {
+            return Err(format!(
+                "landmarks were computed for a{} graph",
+                if self.directed {
+                    " directed"
+                } else {
+                    "n undirected"
+                }
+            ));
+        }
+        if self.node_count != graph.node_count() || self.edge_count != graph.edge_count() {
+            return Err(format!(
+                "landmarks were computed for a graph with {} vertices and {} arcs, but this one has {} and {}",
+                self.node_count,
+                self.edge_count,
+                graph.node_count(),
+                graph.edge_count()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Writes the landmarks in a little-endian binary format read by [`Self::read_from`].
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[self.directed as u8])?;
+        write_u64(writer, self.node_count as u64)?;
+        write_u64(writer, self.edge_count as u64)?;
+        write_u64(writer, self.landmarks.len() as u64)?;
+        for landmark in self.landmarks.iter() {
+            write_u32(writer, landmark.index() as u32)?;
+        }
+        for distances in self.from.iter().chain(self.to.iter()) {
+            for &distance in distances.iter() {
+                write_u64(writer, distance)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads landmarks written by [`Self::write_to`]. The file must be exactly as long as
+    /// its header says, so that a corrupt header cannot ask for more memory than the file
+    /// has bytes.
+    pub fn read_from(reader: &mut (impl Read + Seek)) -> io::Result<Landmarks> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a landmarks file"));
+        }
+        let mut directed = [0];
+        reader.read_exact(&mut directed)?;
+        let directed = directed[0] != 0;
+        let n = read_u64(reader)?;
+        let edge_count = read_u64(reader)? as usize;
+        let count = read_u64(reader)?;
+
+        // 4 bytes per landmark and 8 per distance, in one table or two.
+        let tables = if directed { 2 } else { 1 };
+        let len = count
+            .checked_mul(n)
+            .and_then(|distances| distances.checked_mul(8 * tables))
+            .and_then(|len| len.checked_add(count.checked_mul(4)?));
+        if len != Some(remaining_len(reader)?) {
+            return Err(invalid_data(
+                "the landmarks file does not have the length its header gives",
+            ));
+        }
+        let (n, count) = (n as usize, count as usize);
+
+        let mut landmarks = Vec::with_capacity(count);
+        for _ in 0..count {
+            let landmark = read_u32(reader)? as usize;
+            if landmark >= n {
+                return Err(invalid_data("landmark out of range"));
+            }
+            landmarks.push(NodeIndex::new(landmark));
+        }
+
+        let mut read_distances = || -> io::Result<Vec<Vec<u64>>> {
+            let mut all = Vec::with_capacity(count);
+            for _ in 0..count {
+                let mut distances = Vec::with_capacity(n);
+                for _ in 0..n {
+                    distances.push(read_u64(reader)?);
+                }
+                all.push(distances);
+            }
+            Ok(all)
+        };
+        let from = read_distances()?;
+        let to = if directed {
+            read_distances()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Landmarks {
+            landmarks,
+            directed,
+            node_count: n,
+            edge_count,
+            from,
+            to,
+        })
+    }
+}
+
+impl Potential for Landmarks {
+    fn estimate(&self, node: NodeIndex, target: NodeIndex) -> u64 {
+        self.lower_bound(node.index(), target.index())
+    }
+}
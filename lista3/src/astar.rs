@@ -0,0 +1,142 @@
+use std::collections::BinaryHeap;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeRef, NodeIndexable, VisitMap, Visitable};
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::{NoOrd, ShortestPathTree};
+
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+// Slack for floating point error in the distance function, so that the rounded
+// heuristic never overestimates.
+const SCALE_SLACK: f64 = 1e-6;
+
+/// Lower bound on the cost of reaching `target`, used to direct an A* search.
+///
+/// It must be consistent: for every arc `u -> v` of cost `c`,
+/// `estimate(u, target) <= c + estimate(v, target)`, and `estimate(target, target)` is 0.
+pub trait Potential {
+    fn estimate(&self, node: NodeIndex, target: NodeIndex) -> u64;
+}
+
+/// How the coordinates of a `.co` file are interpreted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Plane coordinates, distance is the length of the straight segment.
+    Euclidean,
+    /// Longitude and latitude in millionths of a degree, as in the DIMACS USA road
+    /// networks; distance is the great-circle distance in meters.
+    GreatCircle,
+}
+
+/// Lower bound on the distance to a fixed target, derived from vertex coordinates.
+///
+/// Geometric distance is multiplied by the smallest ratio of arc cost to arc length
+/// found in the graph, so the estimate is admissible and consistent whatever the
+/// costs mean: meters in `-d` graphs, travel times in `-t` graphs, or anything else.
+pub struct Heuristic {
+    metric: Metric,
+    points: Vec<(f64, f64)>,
+    scale: f64,
+}
+
+impl Heuristic {
+    pub fn new<Ty: EdgeType>(
+        graph: &Graph<(), u64, Ty>,
+        coordinates: &[(i64, i64)],
+        metric: Metric,
+    ) -> Heuristic {
+        let points = coordinates
+            .iter()
+            .map(|&(x, y)| match metric {
+                Metric::Euclidean => (x as f64, y as f64),
+                Metric::GreatCircle => {
+                    ((x as f64 / 1e6).to_radians(), (y as f64 / 1e6).to_radians())
+                }
+            })
+            .collect();
+
+        let mut heuristic = Heuristic {
+            metric,
+            points,
+            scale: 0.0,
+        };
+
+        let scale = graph
+            .edge_references()
+            .filter_map(|edge| {
+                let length = heuristic.distance(edge.source(), edge.target());
+                (length > 0.0).then(|| *edge.weight() as f64 / length)
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        heuristic.scale = if scale.is_finite() {
+            scale * (1.0 - SCALE_SLACK)
+        } else {
+            0.0
+        };
+        heuristic
+    }
+
+    /// Cost per unit of geometric distance used by the estimate.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    fn distance(&self, u: NodeIndex, v: NodeIndex) -> f64 {
+        let (x1, y1) = self.points[u.index()];
+        let (x2, y2) = self.points[v.index()];
+        match self.metric {
+            Metric::Euclidean => (x1 - x2).hypot(y1 - y2),
+            Metric::GreatCircle => {
+                let a = ((y2 - y1) / 2.0).sin().powi(2)
+                    + y1.cos() * y2.cos() * ((x2 - x1) / 2.0).sin().powi(2);
+                2.0 * EARTH_RADIUS_METERS * a.sqrt().min(1.0).asin()
+            }
+        }
+    }
+}
+
+impl Potential for Heuristic {
+    fn estimate(&self, node: NodeIndex, target: NodeIndex) -> u64 {
+        (self.scale * self.distance(node, target)).floor() as u64
+    }
+}
+
+pub fn astar_single<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    end: NodeIndex,
+    heuristic: &impl Potential,
+) -> ShortestPathTree {
+    let mut tree = ShortestPathTree::new(graph.node_bound(), start);
+    let mut visit_next = BinaryHeap::new();
+    let mut visited = graph.visit_map();
+    visit_next.push(NoOrd(heuristic.estimate(start, end), start));
+
+    // The heuristic is consistent, so as in Dijkstra's algorithm a vertex is final
+    // once it leaves the queue.
+    while let Some(NoOrd(_, node)) = visit_next.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+        tree.settled += 1;
+        if node == end {
+            break;
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue;
+            }
+            let next_score = tree.distances[node.index()] + edge.weight();
+            let old_next_score = tree.distances[next.index()];
+            if next_score < old_next_score {
+                tree.relax(node, edge.id(), next, next_score);
+                visit_next.push(NoOrd(next_score + heuristic.estimate(next, end), next));
+            }
+        }
+    }
+    tree
+}
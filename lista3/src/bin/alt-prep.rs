@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use petgraph::{Directed, EdgeType, Undirected};
+
+use lista3::alt::{Landmarks, Selection};
+use lista3::cli::or_exit;
+use lista3::dimacs::read_gr_file;
+
+fn main() {
+    let args = match parse_args() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.undirected {
+        run::<Undirected>(args);
+    } else {
+        run::<Directed>(args);
+    }
+}
+
+fn run<Ty: EdgeType>(args: AppArgs) {
+    let (graph, throughput) = or_exit(read_gr_file::<Ty>(&args.gr_path));
+    eprintln!("Parsed {}.", throughput);
+
+    let selection = if args.avoid {
+        Selection::Avoid
+    } else {
+        Selection::Farthest
+    };
+
+    let now = Instant::now();
+    let landmarks = Landmarks::select(&graph, args.count, selection);
+    let elapsed = now.elapsed();
+
+    let alt_context = |e: std::io::Error| format!("{}: {}", args.alt_path.display(), e);
+    let mut alt_file = BufWriter::new(or_exit(File::create(&args.alt_path).map_err(alt_context)));
+    or_exit(landmarks.write_to(&mut alt_file).map_err(alt_context));
+    or_exit(alt_file.flush().map_err(alt_context));
+
+    println!("f {} {}", args.gr_path.display(), args.alt_path.display());
+    println!("g {} {}", graph.node_count(), graph.edge_count());
+    print!("l");
+    for landmark in landmarks.landmarks.iter() {
+        print!(" {}", landmark.index() + 1);
+    }
+    println!();
+    println!("t {}", elapsed.as_millis());
+}
+
+#[derive(Debug)]
+struct AppArgs {
+    gr_path: PathBuf,
+    alt_path: PathBuf,
+    count: usize,
+    undirected: bool,
+    avoid: bool,
+}
+
+const HELP: &str = "\
+Lista 3 - ALT landmark preprocessing
+
+USAGE:
+  alt-prep -d GR_PATH -o ALT_PATH [OPTIONS]
+
+FLAGS:
+  -h, --help        Prints help information
+  --undirected      Treat arcs of the .gr file as undirected edges
+  --avoid           Select landmarks with the avoid strategy instead of farthest
+
+OPTIONS:
+  -d GR_PATH        Path to .gr file
+  -o ALT_PATH       Path to output landmarks file, used with -alt
+  -k COUNT          Number of landmarks [default: 16]
+
+";
+
+fn parse_args() -> Result<AppArgs, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+
+    // Help has a higher priority and should be handled separately.
+    if pargs.contains(["-h", "--help"]) {
+        print!("{}", HELP);
+        std::process::exit(0);
+    }
+
+    let args = AppArgs {
+        gr_path: pargs.value_from_os_str("-d", parse_path)?,
+        alt_path: pargs.value_from_os_str("-o", parse_path)?,
+        count: pargs.opt_value_from_str("-k")?.unwrap_or(16),
+        undirected: pargs.contains("--undirected"),
+        avoid: pargs.contains("--avoid"),
+    };
+
+    // It's up to the caller what to do with the remaining arguments.
+    let remaining = pargs.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unused arguments left: {:?}.", remaining);
+    }
+
+    Ok(args)
+}
+
+fn parse_path(s: &std::ffi::OsStr) -> Result<PathBuf, &'static str> {
+    Ok(s.into())
+}
@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+
+use lista3::cli::or_exit;
+use lista3::dimacs::read_signed_gr_file;
+use lista3::driver::{run_p2p, run_ss, Answer, CommonArgs};
+use lista3::negative::{
+    bellman_ford, goldberg_radzik, spfa, SignedError, SignedShortestPathTree,
+};
+
+fn main() {
+    let args = match parse_args() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.common.undirected {
+        run::<Undirected>(args);
+    } else {
+        run::<Directed>(args);
+    }
+}
+
+fn solve<Ty: EdgeType>(
+    algorithm: Algorithm,
+    graph: &Graph<(), i64, Ty>,
+    start_node: NodeIndex,
+) -> SignedShortestPathTree {
+    let result = match algorithm {
+        Algorithm::BellmanFord => bellman_ford(graph, start_node),
+        Algorithm::Spfa => spfa(graph, start_node),
+        Algorithm::GoldbergRadzik => goldberg_radzik(graph, start_node),
+    };
+
+    match result {
+        Ok(tree) => tree,
+        Err(SignedError::NegativeCycle(cycle)) => {
+            let nodes: Vec<String> = cycle
+                .nodes
+                .iter()
+                .chain(cycle.nodes.first())
+                .map(|node| (node.index() + 1).to_string())
+                .collect();
+            eprintln!(
+                "Error: negative cycle reachable from vertex {}: {}.",
+                start_node.index() + 1,
+                nodes.join(" ")
+            );
+            std::process::exit(1);
+        }
+        Err(SignedError::Overflow { node }) => {
+            eprintln!(
+                "Error: the distance of vertex {} from vertex {} does not fit in 64 bits.",
+                node.index() + 1,
+                start_node.index() + 1
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run<Ty: EdgeType + Sync>(args: AppArgs) {
+    let common = &args.common;
+    let (graph, throughput) = or_exit(read_signed_gr_file::<Ty>(&common.gr_path));
+    eprintln!("Parsed {}.", throughput);
+
+    if let Some(ss_path) = &common.ss_path {
+        or_exit(run_ss(
+            &graph,
+            common,
+            ss_path,
+            |start_node| solve(args.algorithm, &graph, start_node),
+            SignedShortestPathTree::checksum,
+        ));
+    } else if let Some(p2p_path) = &common.p2p_path {
+        let query = |start_node, end_node: NodeIndex, route: bool| {
+            let tree = solve(args.algorithm, &graph, start_node);
+            Answer {
+                distance: tree.distances[end_node.index()],
+                settled: None,
+                route: if route { tree.path(end_node) } else { None },
+            }
+        };
+        or_exit(run_p2p(&graph, common, p2p_path, query, Vec::new));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Algorithm {
+    BellmanFord,
+    Spfa,
+    GoldbergRadzik,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bellman-ford" => Ok(Algorithm::BellmanFord),
+            "spfa" => Ok(Algorithm::Spfa),
+            "goldberg-radzik" => Ok(Algorithm::GoldbergRadzik),
+            _ => Err(format!("unknown algorithm '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AppArgs {
+    common: CommonArgs,
+    algorithm: Algorithm,
+}
+
+const HELP: &str = "\
+Lista 3 - shortest paths with negative arc costs
+
+USAGE:
+  bellman -d GR_PATH [OPTIONS]
+
+FLAGS:
+  -h, --help        Prints help information
+  --undirected      Treat arcs of the .gr file as undirected edges
+
+OPTIONS:
+  -ss SS_PATH       Path to .ss file
+  -oss OSS_PATH     Path to output file
+  -p2p P2P_PATH     Path to .p2p file
+  -op2p OP2P_PATH   Path to output file
+  -paths PATHS_PATH Path to output file for the routes of -p2p queries
+  -d GR_PATH        Path to .gr file
+  --threads N       Also time the -ss sources on N threads in parallel
+                    (0 for one thread per core)
+  --algo ALGORITHM  bellman-ford, spfa or goldberg-radzik [default: bellman-ford]
+
+A negative cycle reachable from a source is printed and ends the program.
+Results are written in the format of sp, without the c settled lines.
+
+";
+
+fn parse_args() -> Result<AppArgs, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+
+    // Help has a higher priority and should be handled separately.
+    if pargs.contains(["-h", "--help"]) {
+        print!("{}", HELP);
+        std::process::exit(0);
+    }
+
+    let args = AppArgs {
+        common: CommonArgs::parse(&mut pargs)?,
+        algorithm: pargs
+            .opt_value_from_str("--algo")?
+            .unwrap_or(Algorithm::BellmanFord),
+    };
+
+    // It's up to the caller what to do with the remaining arguments.
+    let remaining = pargs.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unused arguments left: {:?}.", remaining);
+    }
+
+    Ok(args)
+}
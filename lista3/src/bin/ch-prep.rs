@@ -0,0 +1,96 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use petgraph::{Directed, EdgeType, Undirected};
+
+use lista3::ch::ContractionHierarchy;
+use lista3::cli::or_exit;
+use lista3::dimacs::read_gr_file;
+
+fn main() {
+    let args = match parse_args() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.undirected {
+        run::<Undirected>(args);
+    } else {
+        run::<Directed>(args);
+    }
+}
+
+fn run<Ty: EdgeType>(args: AppArgs) {
+    let (graph, throughput) = or_exit(read_gr_file::<Ty>(&args.gr_path));
+    eprintln!("Parsed {}.", throughput);
+
+    let now = Instant::now();
+    let hierarchy = ContractionHierarchy::build(&graph);
+    let elapsed = now.elapsed();
+
+    let ch_context = |e: std::io::Error| format!("{}: {}", args.ch_path.display(), e);
+    let mut ch_file = BufWriter::new(or_exit(File::create(&args.ch_path).map_err(ch_context)));
+    or_exit(hierarchy.write_to(&mut ch_file).map_err(ch_context));
+    or_exit(ch_file.flush().map_err(ch_context));
+
+    println!("f {} {}", args.gr_path.display(), args.ch_path.display());
+    println!("g {} {}", graph.node_count(), graph.edge_count());
+    println!("s {}", hierarchy.shortcut_count());
+    println!("t {}", elapsed.as_millis());
+}
+
+#[derive(Debug)]
+struct AppArgs {
+    gr_path: PathBuf,
+    ch_path: PathBuf,
+    undirected: bool,
+}
+
+const HELP: &str = "\
+Lista 3 - Contraction Hierarchy preprocessing
+
+USAGE:
+  ch-prep -d GR_PATH -o CH_PATH [OPTIONS]
+
+FLAGS:
+  -h, --help        Prints help information
+  --undirected      Treat arcs of the .gr file as undirected edges
+
+OPTIONS:
+  -d GR_PATH        Path to .gr file
+  -o CH_PATH        Path to output hierarchy file, used with -ch
+
+";
+
+fn parse_args() -> Result<AppArgs, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+
+    // Help has a higher priority and should be handled separately.
+    if pargs.contains(["-h", "--help"]) {
+        print!("{}", HELP);
+        std::process::exit(0);
+    }
+
+    let args = AppArgs {
+        gr_path: pargs.value_from_os_str("-d", parse_path)?,
+        ch_path: pargs.value_from_os_str("-o", parse_path)?,
+        undirected: pargs.contains("--undirected"),
+    };
+
+    // It's up to the caller what to do with the remaining arguments.
+    let remaining = pargs.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unused arguments left: {:?}.", remaining);
+    }
+
+    Ok(args)
+}
+
+fn parse_path(s: &std::ffi::OsStr) -> Result<PathBuf, &'static str> {
+    Ok(s.into())
+}
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use petgraph::Directed;
+
+use lista3::cache::{cache_path, write_to, Fingerprint};
+use lista3::cli::or_exit;
+use lista3::dimacs::read_gr;
+
+fn main() {
+    let args = match parse_args() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    };
+
+    let bin_path = args.bin_path.unwrap_or_else(|| cache_path(&args.gr_path));
+    let gr_context = |e: &dyn std::fmt::Display| format!("{}: {}", args.gr_path.display(), e);
+    let bin_context = |e: std::io::Error| format!("{}: {}", bin_path.display(), e);
+
+    let now = Instant::now();
+    let gr_file = or_exit(File::open(&args.gr_path).map_err(|e| gr_context(&e)));
+    let graph = read_gr::<Directed, _>(BufReader::new(gr_file)).map_err(|e| gr_context(&e));
+    let graph = or_exit(graph);
+    let source = or_exit(Fingerprint::of_file(&args.gr_path).map_err(|e| gr_context(&e)));
+
+    let mut bin_file = BufWriter::new(or_exit(File::create(&bin_path).map_err(bin_context)));
+    or_exit(write_to(&graph, source, &mut bin_file).map_err(bin_context));
+    or_exit(bin_file.flush().map_err(bin_context));
+    let elapsed = now.elapsed();
+
+    println!("f {} {}", args.gr_path.display(), bin_path.display());
+    println!("g {} {}", graph.node_count(), graph.edge_count());
+    println!("t {}", elapsed.as_millis());
+}
+
+#[derive(Debug)]
+struct AppArgs {
+    gr_path: PathBuf,
+    bin_path: Option<PathBuf>,
+}
+
+const HELP: &str = "\
+Lista 3 - binary cache of .gr files
+
+USAGE:
+  gr2bin -d GR_PATH [OPTIONS]
+
+FLAGS:
+  -h, --help        Prints help information
+
+OPTIONS:
+  -d GR_PATH        Path to .gr file
+  -o BIN_PATH       Path to output file [default: GR_PATH.bin]
+
+The other binaries load GR_PATH.bin instead of GR_PATH while the .gr file is
+unchanged.
+
+";
+
+fn parse_args() -> Result<AppArgs, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+
+    // Help has a higher priority and should be handled separately.
+    if pargs.contains(["-h", "--help"]) {
+        print!("{}", HELP);
+        std::process::exit(0);
+    }
+
+    let args = AppArgs {
+        gr_path: pargs.value_from_os_str("-d", parse_path)?,
+        bin_path: pargs.opt_value_from_os_str("-o", parse_path)?,
+    };
+
+    // It's up to the caller what to do with the remaining arguments.
+    let remaining = pargs.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unused arguments left: {:?}.", remaining);
+    }
+
+    Ok(args)
+}
+
+fn parse_path(s: &std::ffi::OsStr) -> Result<PathBuf, &'static str> {
+    Ok(s.into())
+}
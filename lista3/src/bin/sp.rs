@@ -0,0 +1,5 @@
+use lista3::cli::{main as sp, Algorithm};
+
+fn main() {
+    sp(Algorithm::Dijkstra);
+}
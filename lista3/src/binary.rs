@@ -0,0 +1,50 @@
+// Little-endian helpers shared by the binary file formats of the crate.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+pub(crate) fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+pub(crate) fn write_u32(writer: &mut impl Write, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn write_u64(writer: &mut impl Write, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+// Number of bytes from the current position of `reader` to its end.
+pub(crate) fn remaining_len(reader: &mut impl Seek) -> io::Result<u64> {
+    let position = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(end.saturating_sub(position))
+}
+
+pub(crate) fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+// Reads `count` items with `read`. Counts come from the file, so the vector grows as the
+// items arrive instead of being allocated up front: a corrupt count ends in an
+// `UnexpectedEof` error rather than a failed allocation.
+pub(crate) fn read_vec<R: Read, T>(
+    reader: &mut R,
+    count: usize,
+    mut read: impl FnMut(&mut R) -> io::Result<T>,
+) -> io::Result<Vec<T>> {
+    let mut items = Vec::with_capacity(count.min(1 << 16));
+    for _ in 0..count {
+        items.push(read(reader)?);
+    }
+    Ok(items)
+}
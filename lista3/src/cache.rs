@@ -0,0 +1,202 @@
+// Binary cache of `.gr` files, written by `gr2bin` next to the text file and picked up by
+// `dimacs::read_gr_file` while it is fresh. The layout is little-endian:
+//
+//   magic "LISTA3GR"
+//   u64 vertices, arcs, min cost, max cost
+//   u64 length, modification time (ns since the epoch) and FNV-1a checksum of the `.gr` file
+//   arcs[arcs]   u32 tail, u32 head, u64 cost, in `.gr` file order
+//
+// The solvers work on petgraph graphs whose edge ids are the positions of the arcs in the
+// `.gr` file, and `.ch` files refer to arcs by those ids. Keeping the arcs in file order
+// lets the cache be read straight into a graph with the same ids, one arc at a time.
+
+use std::ffi::OsString;
+use std::fs::{File, Metadata};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{EdgeType, Graph};
+
+use crate::binary::{invalid_data, read_u32, read_u64, write_u32, write_u64};
+
+const MAGIC: &[u8; 8] = b"LISTA3GR";
+const HEADER_LEN: u64 = 64;
+const ARC_LEN: u64 = 16;
+
+/// Where the binary cache of the `.gr` file at `gr_path` lives: the same path with `.bin`
+/// appended.
+pub fn cache_path(gr_path: &Path) -> PathBuf {
+    let mut path = OsString::from(gr_path);
+    path.push(".bin");
+    path.into()
+}
+
+/// What the cache remembers of its `.gr` file to tell whether it is still fresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub len: u64,
+    pub modified: u64,
+    pub checksum: u64,
+}
+
+fn modified(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+/// 64-bit FNV-1a hash of everything `reader` yields.
+pub fn checksum(mut reader: impl Read) -> io::Result<u64> {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut buffer = vec![0; 1 << 16];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        for &byte in &buffer[..read] {
+            hash = (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl Fingerprint {
+    pub fn of_file(path: &Path) -> io::Result<Fingerprint> {
+        let file = File::open(path)?;
+        let metadata = file.metadata()?;
+        Ok(Fingerprint {
+            len: metadata.len(),
+            modified: modified(&metadata),
+            checksum: checksum(BufReader::new(file))?,
+        })
+    }
+}
+
+/// Size and cost range of a cached graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub node_count: usize,
+    pub arc_count: usize,
+    pub min_cost: u64,
+    pub max_cost: u64,
+    pub source: Fingerprint,
+}
+
+/// Writes `graph`, read from a `.gr` file with fingerprint `source`, in the cache format.
+pub fn write_to<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    source: Fingerprint,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    write_u64(writer, graph.node_count() as u64)?;
+    write_u64(writer, graph.edge_count() as u64)?;
+    write_u64(writer, graph.edge_weights().copied().min().unwrap_or(0))?;
+    write_u64(writer, graph.edge_weights().copied().max().unwrap_or(0))?;
+    write_u64(writer, source.len)?;
+    write_u64(writer, source.modified)?;
+    write_u64(writer, source.checksum)?;
+    for edge in graph.raw_edges() {
+        write_u32(writer, edge.source().index() as u32)?;
+        write_u32(writer, edge.target().index() as u32)?;
+        write_u64(writer, edge.weight)?;
+    }
+    Ok(())
+}
+
+/// A cache file whose header has been read, checked to be as long as the header says.
+pub struct CacheFile {
+    reader: BufReader<File>,
+    header: Header,
+}
+
+impl CacheFile {
+    pub fn open(path: &Path) -> io::Result<CacheFile> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        if len < HEADER_LEN {
+            return Err(invalid_data("not a binary graph file"));
+        }
+        let mut reader = BufReader::new(file);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a binary graph file"));
+        }
+
+        let mut fields = [0; 7];
+        for field in fields.iter_mut() {
+            *field = read_u64(&mut reader)?;
+        }
+        let [node_count, arc_count, min_cost, max_cost, source_len, modified, checksum] = fields;
+        if node_count > u32::MAX as u64
+            || arc_count > u32::MAX as u64
+            || HEADER_LEN + ARC_LEN * arc_count != len
+        {
+            return Err(invalid_data("truncated binary graph file"));
+        }
+        let header = Header {
+            node_count: node_count as usize,
+            arc_count: arc_count as usize,
+            min_cost,
+            max_cost,
+            source: Fingerprint {
+                len: source_len,
+                modified,
+                checksum,
+            },
+        };
+        Ok(CacheFile { reader, header })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Whether the cache was written for the `.gr` file at `gr_path` as it is now. The
+    /// checksum is only computed if the length matches but the modification time does not.
+    pub fn is_fresh_for(&self, gr_path: &Path) -> io::Result<bool> {
+        let metadata = std::fs::metadata(gr_path)?;
+        let source = self.header.source;
+        if metadata.len() != source.len {
+            return Ok(false);
+        }
+        if modified(&metadata) == source.modified {
+            return Ok(true);
+        }
+        Ok(Fingerprint::of_file(gr_path)?.checksum == source.checksum)
+    }
+
+    /// Reads the arcs into a petgraph graph, in `.gr` file order as [`crate::dimacs::read_gr`]
+    /// would. Every vertex must be in range and the costs must span exactly the range in the
+    /// header, which callers rely on to convert them with `weight`.
+    pub fn read_graph<W, Ty: EdgeType>(
+        mut self,
+        weight: impl Fn(u64) -> W,
+    ) -> io::Result<Graph<(), W, Ty>> {
+        let (n, m) = (self.header.node_count, self.header.arc_count);
+        let mut graph = Graph::with_capacity(n, m);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        let mut range: Option<(u64, u64)> = None;
+        for _ in 0..m {
+            let tail = read_u32(&mut self.reader)? as usize;
+            let head = read_u32(&mut self.reader)? as usize;
+            let cost = read_u64(&mut self.reader)?;
+            if tail >= n || head >= n {
+                return Err(invalid_data("corrupt binary graph file"));
+            }
+            range = Some(range.map_or((cost, cost), |(min, max)| (min.min(cost), max.max(cost))));
+            graph.add_edge(NodeIndex::new(tail), NodeIndex::new(head), weight(cost));
+        }
+        if range.unwrap_or((0, 0)) != (self.header.min_cost, self.header.max_cost) {
+            return Err(invalid_data("corrupt binary graph file"));
+        }
+        Ok(graph)
+    }
+}
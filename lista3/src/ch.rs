@@ -0,0 +1,709 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::io::{self, Read, Write};
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::{NoOrd, Route};
+use crate::binary::{invalid_data, read_u32, read_u64, read_vec, write_u32, write_u64};
+
+const MAGIC: &[u8; 8] = b"LISTA3CH";
+
+// Witness searches give up after settling this many vertices; a missed witness only
+// costs an unnecessary shortcut. Estimating priorities uses the cheaper limit.
+const WITNESS_SETTLE_LIMIT: usize = 500;
+const ESTIMATE_SETTLE_LIMIT: usize = 50;
+
+const VIA_NODE: u32 = 1 << 31;
+
+/// What an arc of the hierarchy stands for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Via {
+    /// An arc of the input graph.
+    Edge(EdgeIndex),
+    /// A shortcut through a vertex contracted before both of its endpoints.
+    Node(usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Arc {
+    head: usize,
+    cost: u64,
+    via: Via,
+}
+
+// Arcs grouped by tail, `arcs[first[v]..first[v + 1]]` leave `v`.
+struct Csr {
+    first: Vec<usize>,
+    arcs: Vec<Arc>,
+}
+
+impl Csr {
+    fn from_lists(lists: Vec<Vec<Arc>>) -> Csr {
+        let mut first = Vec::with_capacity(lists.len() + 1);
+        first.push(0);
+        let mut arcs = Vec::new();
+        for list in lists {
+            arcs.extend(list);
+            first.push(arcs.len());
+        }
+        Csr { first, arcs }
+    }
+
+    fn arcs(&self, node: usize) -> &[Arc] {
+        &self.arcs[self.first[node]..self.first[node + 1]]
+    }
+}
+
+/// Contraction Hierarchy of a graph: every vertex gets a rank, and a shortest path can
+/// always be found going up the ranks from the source and from the target.
+///
+/// `upward` holds arcs `v -> w` with `rank[w] > rank[v]`, `downward` holds arcs `u -> v`
+/// with `rank[u] > rank[v]`, stored at `v` so the backward search can follow them upwards.
+pub struct ContractionHierarchy {
+    pub rank: Vec<usize>,
+    directed: bool,
+    edge_count: usize,
+    upward: Csr,
+    downward: Csr,
+}
+
+// Remaining graph during contraction, with at most one arc per ordered pair of vertices.
+struct Overlay {
+    outgoing: Vec<Vec<Arc>>,
+    incoming: Vec<Vec<Arc>>,
+    contracted: Vec<bool>,
+    contracted_neighbours: Vec<usize>,
+}
+
+impl Overlay {
+    fn add_arc(&mut self, tail: usize, head: usize, cost: u64, via: Via) {
+        if tail == head {
+            return;
+        }
+        match self.outgoing[tail].iter().position(|arc| arc.head == head) {
+            Some(i) if self.outgoing[tail][i].cost <= cost => {}
+            Some(i) => {
+                self.outgoing[tail][i] = Arc { head, cost, via };
+                let j = self.incoming[head]
+                    .iter()
+                    .position(|arc| arc.head == tail)
+                    .unwrap();
+                self.incoming[head][j] = Arc {
+                    head: tail,
+                    cost,
+                    via,
+                };
+            }
+            None => {
+                self.outgoing[tail].push(Arc { head, cost, via });
+                self.incoming[head].push(Arc {
+                    head: tail,
+                    cost,
+                    via,
+                });
+            }
+        }
+    }
+
+    // Shortcuts `(u, w, cost)` needed to keep distances when `node` is removed.
+    fn shortcuts(
+        &self,
+        node: usize,
+        witness: &mut WitnessSearch,
+        settle_limit: usize,
+    ) -> Vec<(usize, usize, u64)> {
+        let mut shortcuts = Vec::new();
+        for incoming in self.incoming[node].iter() {
+            let limit = self.outgoing[node]
+                .iter()
+                .map(|outgoing| incoming.cost + outgoing.cost)
+                .max();
+            let Some(limit) = limit else {
+                break;
+            };
+            witness.run(
+                self,
+                incoming.head,
+                node,
+                limit,
+                settle_limit,
+                &self.outgoing[node],
+            );
+            for outgoing in self.outgoing[node].iter() {
+                if outgoing.head == incoming.head {
+                    continue;
+                }
+                let cost = incoming.cost + outgoing.cost;
+                if witness.distance(outgoing.head) > cost {
+                    shortcuts.push((incoming.head, outgoing.head, cost));
+                }
+            }
+        }
+        shortcuts
+    }
+
+    fn priority(&self, node: usize, witness: &mut WitnessSearch) -> i64 {
+        let removed = self.incoming[node].len() + self.outgoing[node].len();
+        let added = self.shortcuts(node, witness, ESTIMATE_SETTLE_LIMIT).len();
+        added as i64 - removed as i64 + self.contracted_neighbours[node] as i64
+    }
+
+    fn contract(&mut self, node: usize, witness: &mut WitnessSearch) -> (Vec<Arc>, Vec<Arc>) {
+        for (tail, head, cost) in self.shortcuts(node, witness, WITNESS_SETTLE_LIMIT) {
+            self.add_arc(tail, head, cost, Via::Node(node));
+        }
+
+        self.contracted[node] = true;
+        let outgoing = std::mem::take(&mut self.outgoing[node]);
+        let incoming = std::mem::take(&mut self.incoming[node]);
+        for arc in outgoing.iter() {
+            self.incoming[arc.head].retain(|back| back.head != node);
+            self.contracted_neighbours[arc.head] += 1;
+        }
+        for arc in incoming.iter() {
+            self.outgoing[arc.head].retain(|back| back.head != node);
+            self.contracted_neighbours[arc.head] += 1;
+        }
+        (outgoing, incoming)
+    }
+}
+
+// Local Dijkstra in the remaining graph that avoids the vertex being contracted.
+struct WitnessSearch {
+    distances: Vec<u64>,
+    touched: Vec<usize>,
+    is_target: Vec<bool>,
+}
+
+impl WitnessSearch {
+    fn new(node_count: usize) -> WitnessSearch {
+        WitnessSearch {
+            distances: vec![u64::MAX; node_count],
+            touched: Vec::new(),
+            is_target: vec![false; node_count],
+        }
+    }
+
+    fn distance(&self, node: usize) -> u64 {
+        self.distances[node]
+    }
+
+    // Stops once every head of `targets` is settled, or when it is clear no path
+    // within `limit` is left to find.
+    fn run(
+        &mut self,
+        overlay: &Overlay,
+        start: usize,
+        avoid: usize,
+        limit: u64,
+        settle_limit: usize,
+        targets: &[Arc],
+    ) {
+        for node in self.touched.drain(..) {
+            self.distances[node] = u64::MAX;
+        }
+        let mut remaining = 0;
+        for arc in targets {
+            if !self.is_target[arc.head] {
+                self.is_target[arc.head] = true;
+                remaining += 1;
+            }
+        }
+
+        let mut visit_next = BinaryHeap::new();
+        self.distances[start] = 0;
+        self.touched.push(start);
+        visit_next.push(NoOrd(0, start));
+
+        let mut settled = 0;
+        while let Some(NoOrd(score, node)) = visit_next.pop() {
+            if score > self.distances[node] {
+                continue;
+            }
+            if score > limit || settled == settle_limit || remaining == 0 {
+                break;
+            }
+            settled += 1;
+            if self.is_target[node] {
+                remaining -= 1;
+            }
+            for arc in overlay.outgoing[node].iter() {
+                if arc.head == avoid {
+                    continue;
+                }
+                let next_score = score + arc.cost;
+                if next_score < self.distances[arc.head] {
+                    if self.distances[arc.head] == u64::MAX {
+                        self.touched.push(arc.head);
+                    }
+                    self.distances[arc.head] = next_score;
+                    visit_next.push(NoOrd(next_score, arc.head));
+                }
+            }
+        }
+
+        for arc in targets {
+            self.is_target[arc.head] = false;
+        }
+    }
+}
+
+// One direction of an upward search. Only the `touched` vertices have a finite distance,
+// a parent or are settled, so a query resets them instead of the whole arrays.
+struct SearchSide {
+    distances: Vec<u64>,
+    parents: Vec<Option<(usize, Arc)>>,
+    settled: Vec<bool>,
+    touched: Vec<usize>,
+    visit_next: BinaryHeap<NoOrd<u64, usize>>,
+}
+
+impl SearchSide {
+    fn new(node_count: usize) -> SearchSide {
+        SearchSide {
+            distances: vec![u64::MAX; node_count],
+            parents: vec![None; node_count],
+            settled: vec![false; node_count],
+            touched: Vec::new(),
+            visit_next: BinaryHeap::new(),
+        }
+    }
+
+    fn reset(&mut self, start: usize) {
+        for node in self.touched.drain(..) {
+            self.distances[node] = u64::MAX;
+            self.parents[node] = None;
+            self.settled[node] = false;
+        }
+        self.visit_next.clear();
+        self.distances[start] = 0;
+        self.touched.push(start);
+        self.visit_next.push(NoOrd(0, start));
+    }
+}
+
+/// Answer to a query of a [`ContractionHierarchy`].
+pub struct HierarchyPath {
+    /// `u64::MAX` if the target cannot be reached.
+    pub distance: u64,
+    /// Arcs of the input graph along a shortest route, with the shortcuts unpacked.
+    pub route: Option<Route>,
+    /// Number of vertices settled by both searches.
+    pub settled: usize,
+}
+
+/// Bidirectional upward search in a [`ContractionHierarchy`], keeping its arrays between
+/// queries so that each one only costs the vertices it reaches.
+pub struct UpwardSearch<'a> {
+    hierarchy: &'a ContractionHierarchy,
+    sides: [SearchSide; 2],
+}
+
+impl UpwardSearch<'_> {
+    /// Shortest route from `start` to `end`.
+    pub fn query(&mut self, start: NodeIndex, end: NodeIndex) -> HierarchyPath {
+        let hierarchy = self.hierarchy;
+        self.sides[0].reset(start.index());
+        self.sides[1].reset(end.index());
+
+        let mut best = u64::MAX;
+        let mut meeting = None;
+        let mut settled = 0;
+
+        // Each search stops on its own once its minimum reaches the best path found:
+        // an upward path cannot get shorter past that point.
+        loop {
+            let side = match (
+                self.sides[0].visit_next.peek(),
+                self.sides[1].visit_next.peek(),
+            ) {
+                (Some(&NoOrd(forward, _)), Some(&NoOrd(backward, _))) => {
+                    if forward <= backward {
+                        0
+                    } else {
+                        1
+                    }
+                }
+                (Some(_), None) => 0,
+                (None, Some(_)) => 1,
+                (None, None) => break,
+            };
+            let [forward, backward] = &mut self.sides;
+            let (this, other) = if side == 0 {
+                (forward, backward)
+            } else {
+                (backward, forward)
+            };
+
+            let NoOrd(score, node) = this.visit_next.pop().unwrap();
+            if this.settled[node] || score > this.distances[node] {
+                continue;
+            }
+            if score >= best {
+                this.visit_next.clear();
+                continue;
+            }
+            this.settled[node] = true;
+            settled += 1;
+
+            let other_score = other.distances[node];
+            if other_score != u64::MAX && score + other_score < best {
+                best = score + other_score;
+                meeting = Some(node);
+            }
+
+            let arcs = if side == 0 {
+                hierarchy.upward.arcs(node)
+            } else {
+                hierarchy.downward.arcs(node)
+            };
+            for arc in arcs {
+                let next_score = score + arc.cost;
+                if next_score < this.distances[arc.head] {
+                    if this.distances[arc.head] == u64::MAX {
+                        this.touched.push(arc.head);
+                    }
+                    this.distances[arc.head] = next_score;
+                    this.parents[arc.head] = Some((node, *arc));
+                    this.visit_next.push(NoOrd(next_score, arc.head));
+                }
+            }
+        }
+
+        let route = meeting.map(|meeting| self.route(start, meeting));
+        HierarchyPath {
+            distance: best,
+            route,
+            settled,
+        }
+    }
+
+    // The route through `meeting`, following the parents of both searches away from it.
+    fn route(&self, start: NodeIndex, meeting: usize) -> Route {
+        // Hierarchy arcs along the route, as (tail, head, cost, via).
+        let mut arcs = Vec::new();
+        let mut current = meeting;
+        while let Some((previous, arc)) = self.sides[0].parents[current] {
+            arcs.push((previous, current, arc.cost, arc.via));
+            current = previous;
+        }
+        arcs.reverse();
+        let mut current = meeting;
+        while let Some((following, arc)) = self.sides[1].parents[current] {
+            arcs.push((current, following, arc.cost, arc.via));
+            current = following;
+        }
+
+        let mut edges = Vec::new();
+        for (tail, head, cost, via) in arcs {
+            self.hierarchy.unpack(tail, head, cost, via, &mut edges);
+        }
+
+        // Coming back to a vertex already on the route means a zero-cost cycle, which is
+        // cut out.
+        let mut route = Route {
+            nodes: vec![start],
+            edges: Vec::new(),
+        };
+        let mut position = HashMap::from([(start.index(), 0)]);
+        for (_, head, _, edge) in edges {
+            if let Some(&i) = position.get(&head) {
+                for node in route.nodes.drain(i + 1..) {
+                    position.remove(&node.index());
+                }
+                route.edges.truncate(i);
+                continue;
+            }
+            position.insert(head, route.nodes.len());
+            route.nodes.push(NodeIndex::new(head));
+            route.edges.push(edge);
+        }
+        route
+    }
+}
+
+impl ContractionHierarchy {
+    /// Contracts the vertices of `graph` one by one, cheapest first, where the price of
+    /// a vertex is its edge difference (shortcuts added minus arcs removed) plus the
+    /// number of its neighbours already contracted, to spread contraction evenly.
+    pub fn build<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> ContractionHierarchy {
+        let n = graph.node_count();
+        let mut overlay = Overlay {
+            outgoing: vec![Vec::new(); n],
+            incoming: vec![Vec::new(); n],
+            contracted: vec![false; n],
+            contracted_neighbours: vec![0; n],
+        };
+        for edge in graph.edge_references() {
+            let (u, v) = (edge.source().index(), edge.target().index());
+            overlay.add_arc(u, v, *edge.weight(), Via::Edge(edge.id()));
+            if !graph.is_directed() {
+                overlay.add_arc(v, u, *edge.weight(), Via::Edge(edge.id()));
+            }
+        }
+
+        let mut witness = WitnessSearch::new(n);
+        let mut queue = BinaryHeap::new();
+        for node in 0..n {
+            queue.push(NoOrd(overlay.priority(node, &mut witness), node));
+        }
+
+        let mut rank = vec![0; n];
+        let mut upward = vec![Vec::new(); n];
+        let mut downward = vec![Vec::new(); n];
+        let mut next_rank = 0;
+
+        while let Some(NoOrd(_, node)) = queue.pop() {
+            if overlay.contracted[node] {
+                continue;
+            }
+            // Lazy update: priorities go stale as neighbours are contracted.
+            let priority = overlay.priority(node, &mut witness);
+            if let Some(&NoOrd(next_priority, _)) = queue.peek() {
+                if priority > next_priority {
+                    queue.push(NoOrd(priority, node));
+                    continue;
+                }
+            }
+
+            rank[node] = next_rank;
+            next_rank += 1;
+
+            let (outgoing, incoming) = overlay.contract(node, &mut witness);
+            let mut neighbours: Vec<usize> = outgoing
+                .iter()
+                .chain(incoming.iter())
+                .map(|arc| arc.head)
+                .collect();
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            for neighbour in neighbours {
+                let priority = overlay.priority(neighbour, &mut witness);
+                queue.push(NoOrd(priority, neighbour));
+            }
+            upward[node] = outgoing;
+            downward[node] = incoming;
+        }
+
+        ContractionHierarchy {
+            rank,
+            directed: graph.is_directed(),
+            edge_count: graph.edge_count(),
+            upward: Csr::from_lists(upward),
+            downward: Csr::from_lists(downward),
+        }
+    }
+
+    /// Number of arcs in the hierarchy that are not arcs of the input graph.
+    pub fn shortcut_count(&self) -> usize {
+        self.upward
+            .arcs
+            .iter()
+            .chain(self.downward.arcs.iter())
+            .filter(|arc| matches!(arc.via, Via::Node(_)))
+            .count()
+    }
+
+    /// Checks that the hierarchy was built for a graph of this shape.
+    pub fn validate<Ty: EdgeType>(&self, graph: &Graph<(), u64, Ty>) -> Result<(), String> {
+        if self.directed != graph.is_directed() {
+            return Err(format!(
+                "hierarchy was built for a{} graph",
+                if self.directed {
+                    " directed"
+                } else {
+                    "n undirected"
+                }
+            ));
+        }
+        if self.rank.len() != graph.node_count() || self.edge_count != graph.edge_count() {
+            return Err(format!(
+                "hierarchy was built for a graph with {} vertices and {} arcs, but this one has {} and {}",
+                self.rank.len(),
+                self.edge_count,
+                graph.node_count(),
+                graph.edge_count()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reusable state for queries of this hierarchy.
+    pub fn search(&self) -> UpwardSearch<'_> {
+        let n = self.rank.len();
+        UpwardSearch {
+            hierarchy: self,
+            sides: [SearchSide::new(n), SearchSide::new(n)],
+        }
+    }
+
+    // Appends the input arcs, as (tail, head, cost, edge), that the arc `tail -> head` stands for.
+    fn unpack(
+        &self,
+        tail: usize,
+        head: usize,
+        cost: u64,
+        via: Via,
+        edges: &mut Vec<(usize, usize, u64, EdgeIndex)>,
+    ) {
+        // Shortcuts nest as deep as the hierarchy, so they are unpacked without recursion;
+        // the second half of a shortcut is pushed first to come out after the first.
+        let mut stack = vec![(tail, head, cost, via)];
+        while let Some((tail, head, cost, via)) = stack.pop() {
+            match via {
+                Via::Edge(edge) => edges.push((tail, head, cost, edge)),
+                Via::Node(middle) => {
+                    // Both halves were arcs of `middle` when it was contracted.
+                    let first = self
+                        .downward
+                        .arcs(middle)
+                        .iter()
+                        .find(|arc| arc.head == tail);
+                    let second = self.upward.arcs(middle).iter().find(|arc| arc.head == head);
+                    let (first, second) = (first.unwrap(), second.unwrap());
+                    stack.push((middle, head, second.cost, second.via));
+                    stack.push((tail, middle, first.cost, first.via));
+                }
+            }
+        }
+    }
+
+    /// Writes the hierarchy in a little-endian binary format read by [`Self::read_from`].
+    ///
+    /// Fails without writing anything if a vertex or arc index does not fit in 31 bits: the
+    /// top bit of an arc's `via` field tells shortcuts from input arcs.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let arcs = self.upward.arcs.iter().chain(self.downward.arcs.iter());
+        let indices = arcs.map(|arc| match arc.via {
+            Via::Edge(edge) => edge.index(),
+            Via::Node(node) => node,
+        });
+        if indices
+            .max()
+            .is_some_and(|index| index >= VIA_NODE as usize)
+        {
+            return Err(invalid_data(
+                "hierarchy files hold at most 2^31 vertices and 2^31 arcs",
+            ));
+        }
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[self.directed as u8])?;
+        write_u64(writer, self.rank.len() as u64)?;
+        write_u64(writer, self.edge_count as u64)?;
+        for &rank in self.rank.iter() {
+            write_u32(writer, rank as u32)?;
+        }
+        for csr in [&self.upward, &self.downward] {
+            write_u64(writer, csr.arcs.len() as u64)?;
+            for &first in csr.first.iter() {
+                write_u64(writer, first as u64)?;
+            }
+            for arc in csr.arcs.iter() {
+                write_u32(writer, arc.head as u32)?;
+                write_u64(writer, arc.cost)?;
+                write_u32(
+                    writer,
+                    match arc.via {
+                        Via::Edge(edge) => edge.index() as u32,
+                        Via::Node(node) => node as u32 | VIA_NODE,
+                    },
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> io::Result<ContractionHierarchy> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a contraction hierarchy file"));
+        }
+        let mut directed = [0];
+        reader.read_exact(&mut directed)?;
+        let n = read_u64(reader)?;
+        let edge_count = read_u64(reader)?;
+        if n > VIA_NODE as u64 || edge_count > VIA_NODE as u64 {
+            return Err(invalid_data(
+                "hierarchy files hold at most 2^31 vertices and 2^31 arcs",
+            ));
+        }
+        let (n, edge_count) = (n as usize, edge_count as usize);
+
+        let rank = read_vec(reader, n, |reader| Ok(read_u32(reader)? as usize))?;
+
+        let mut csrs = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let arc_count = read_u64(reader)? as usize;
+            let first = read_vec(reader, n + 1, |reader| Ok(read_u64(reader)? as usize))?;
+            let arcs = read_vec(reader, arc_count, |reader| {
+                let head = read_u32(reader)? as usize;
+                let cost = read_u64(reader)?;
+                let via = read_u32(reader)?;
+                let via = if via & VIA_NODE != 0 {
+                    Via::Node((via & !VIA_NODE) as usize)
+                } else {
+                    Via::Edge(EdgeIndex::new(via as usize))
+                };
+                if head >= n || matches!(via, Via::Node(node) if node >= n) {
+                    return Err(invalid_data("arc refers to a vertex out of range"));
+                }
+                Ok(Arc { head, cost, via })
+            })?;
+            if first.windows(2).any(|w| w[0] > w[1]) || first[n] != arc_count {
+                return Err(invalid_data("malformed arc offsets"));
+            }
+            csrs.push(Csr { first, arcs });
+        }
+        let downward = csrs.pop().unwrap();
+        let upward = csrs.pop().unwrap();
+
+        let hierarchy = ContractionHierarchy {
+            rank,
+            directed: directed[0] != 0,
+            edge_count,
+            upward,
+            downward,
+        };
+        hierarchy.check_vias()?;
+        Ok(hierarchy)
+    }
+
+    // Every input arc must be in range and every shortcut must have both of its halves,
+    // through a vertex ranked below both of its endpoints, so that `unpack` finds them and
+    // comes to an end.
+    fn check_vias(&self) -> io::Result<()> {
+        for v in 0..self.rank.len() {
+            let upward = self.upward.arcs(v).iter().map(|arc| (v, arc.head, arc.via));
+            let downward = self
+                .downward
+                .arcs(v)
+                .iter()
+                .map(|arc| (arc.head, v, arc.via));
+            for (tail, head, via) in upward.chain(downward) {
+                match via {
+                    Via::Edge(edge) if edge.index() >= self.edge_count => {
+                        return Err(invalid_data("arc refers to an input arc out of range"));
+                    }
+                    Via::Edge(_) => {}
+                    Via::Node(middle) => {
+                        let below = self.rank[middle] < self.rank[tail].min(self.rank[head]);
+                        let first = self
+                            .downward
+                            .arcs(middle)
+                            .iter()
+                            .any(|arc| arc.head == tail);
+                        let second = self.upward.arcs(middle).iter().any(|arc| arc.head == head);
+                        if !(below && first && second) {
+                            return Err(invalid_data("shortcut without both of its halves"));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
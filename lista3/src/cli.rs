@@ -0,0 +1,478 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use indicatif::ProgressBar;
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+
+use crate::algorithms::{bidirectional_dijkstra_single, dijkstra_single, ShortestPathTree};
+use crate::alt::Landmarks;
+use crate::astar::{astar_single, Heuristic, Metric};
+use crate::ch::ContractionHierarchy;
+use crate::dimacs::read_gr_file;
+use crate::driver::{parse_path, run_p2p, run_ss, Answer, CommonArgs};
+use crate::heap::{DaryHeap, FibonacciHeap, PairingHeap};
+use crate::parsing::{parse_co, parse_file, parse_p2p, parse_ss};
+use crate::solver::{DeltaStepping, Dial, Dijkstra, DijkstraWith, Radix, ShortestPathSolver};
+use crate::verify::{check_optimality, first_mismatch, Mismatch};
+
+/// Algorithms selectable with `--algo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Dijkstra,
+    Dial,
+    Radix,
+    DeltaStepping,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dijkstra" => Ok(Algorithm::Dijkstra),
+            "dial" => Ok(Algorithm::Dial),
+            "radix" => Ok(Algorithm::Radix),
+            "delta-stepping" => Ok(Algorithm::DeltaStepping),
+            _ => Err(format!("unknown algorithm '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Algorithm::Dijkstra => "dijkstra",
+            Algorithm::Dial => "dial",
+            Algorithm::Radix => "radix",
+            Algorithm::DeltaStepping => "delta-stepping",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Priority queues of Dijkstra's algorithm selectable with `--heap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heap {
+    Binary,
+    Dary2,
+    Dary4,
+    Dary8,
+    Pairing,
+    Fibonacci,
+}
+
+impl FromStr for Heap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "binary" => Ok(Heap::Binary),
+            "2-ary" => Ok(Heap::Dary2),
+            "4-ary" => Ok(Heap::Dary4),
+            "8-ary" => Ok(Heap::Dary8),
+            "pairing" => Ok(Heap::Pairing),
+            "fibonacci" => Ok(Heap::Fibonacci),
+            _ => Err(format!("unknown heap '{}'", s)),
+        }
+    }
+}
+
+/// Returns the value, or prints the error and ends the program.
+pub fn or_exit<T>(result: Result<T, String>) -> T {
+    match result {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Reads a file written by ch-prep or alt-prep with `read`, prefixing errors with the path.
+fn read_file<T>(
+    path: &Path,
+    read: impl FnOnce(&mut BufReader<File>) -> io::Result<T>,
+) -> Result<T, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    read(&mut BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn make_solver<Ty: EdgeType>(
+    algorithm: Algorithm,
+    heap: Option<Heap>,
+    delta: Option<u64>,
+    graph: &Graph<(), u64, Ty>,
+) -> Box<dyn ShortestPathSolver<Ty>> {
+    match algorithm {
+        Algorithm::Dijkstra => match heap.unwrap_or(Heap::Binary) {
+            Heap::Binary => Box::new(Dijkstra),
+            Heap::Dary2 => Box::new(DijkstraWith::<DaryHeap<2>>::new()),
+            Heap::Dary4 => Box::new(DijkstraWith::<DaryHeap<4>>::new()),
+            Heap::Dary8 => Box::new(DijkstraWith::<DaryHeap<8>>::new()),
+            Heap::Pairing => Box::new(DijkstraWith::<PairingHeap>::new()),
+            Heap::Fibonacci => Box::new(DijkstraWith::<FibonacciHeap>::new()),
+        },
+        Algorithm::Dial => Box::new(Dial::for_graph(graph)),
+        Algorithm::Radix => Box::new(Radix),
+        Algorithm::DeltaStepping => Box::new(match delta {
+            Some(delta) => DeltaStepping { delta },
+            None => DeltaStepping::for_graph(graph),
+        }),
+    }
+}
+
+// Runs every algorithm from the source of every query and compares the full distance
+// vectors with those of the first algorithm, checks the optimality conditions of every
+// tree, and for point-to-point queries checks that the early-exit searches find the same
+// distance to the target. Prints one line per problem and returns their number.
+fn verify<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    solvers: &[(Algorithm, Box<dyn ShortestPathSolver<Ty>>)],
+    queries: &[(usize, Option<usize>)],
+) -> usize {
+    let mut problems = 0;
+    let mut report = |source: usize, message: String| {
+        println!("x {} {}", source + 1, message);
+        problems += 1;
+    };
+
+    let bar = ProgressBar::new(queries.len() as u64);
+    for &(source, target) in queries {
+        bar.inc(1);
+
+        let start_node = NodeIndex::new(source);
+        let trees: Vec<_> = solvers
+            .iter()
+            .map(|(_, solver)| solver.solve_all(graph, start_node))
+            .collect();
+
+        for ((algorithm, solver), tree) in solvers.iter().zip(&trees) {
+            if let Some(mismatch) = first_mismatch(&trees[0].distances, &tree.distances) {
+                report(
+                    source,
+                    format!("{} and {} differ at {}", solvers[0].0, algorithm, mismatch),
+                );
+            }
+            if let Err(e) = check_optimality(graph, start_node, tree) {
+                report(source, format!("{}: {}", algorithm, e));
+            }
+            if let Some(target) = target {
+                let end_node = NodeIndex::new(target);
+                let single = solver.solve_single(graph, start_node, end_node);
+                let mismatch = Mismatch {
+                    vertex: end_node,
+                    left: tree.distances[target],
+                    right: single.distances[target],
+                };
+                if mismatch.left != mismatch.right {
+                    report(
+                        source,
+                        format!("{} and its early exit differ at {}", algorithm, mismatch),
+                    );
+                }
+            }
+        }
+    }
+    bar.finish();
+
+    let names: Vec<String> = solvers
+        .iter()
+        .map(|(algorithm, _)| algorithm.to_string())
+        .collect();
+    println!(
+        "c verified {} against {} on {} queries: {} problems",
+        names[1..].join(", "),
+        names[0],
+        queries.len(),
+        problems
+    );
+    problems
+}
+
+/// Entry point of the `sp` binary and of its aliases, which only differ in the algorithm
+/// used when `--algo` is not given.
+pub fn main(default: Algorithm) {
+    let args = match parse_args(default) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error: {}.", e);
+            std::process::exit(1);
+        }
+    };
+
+    let modes = [
+        args.co_path.is_some(),
+        args.bidirectional,
+        args.ch_path.is_some(),
+        args.alt_path.is_some(),
+    ];
+    if modes.iter().filter(|&&mode| mode).count() > 1 {
+        eprintln!("Error: only one of -co, --bidirectional, -ch and -alt can be given.");
+        std::process::exit(1);
+    }
+
+    if args.verify.is_some() && (modes.contains(&true) || args.common.threads.is_some()) {
+        eprintln!(
+            "Error: --verify cannot be combined with -co, --bidirectional, -ch, -alt or --threads."
+        );
+        std::process::exit(1);
+    }
+
+    if args
+        .verify
+        .as_ref()
+        .is_some_and(|algorithms| algorithms.len() < 2)
+    {
+        eprintln!("Error: --verify needs at least two algorithms.");
+        std::process::exit(1);
+    }
+
+    if args.verify.is_some() && args.common.ss_path.is_none() && args.common.p2p_path.is_none() {
+        eprintln!("Error: --verify needs -ss or -p2p.");
+        std::process::exit(1);
+    }
+
+    // With --verify, --algo is ignored and the options apply to the algorithms compared.
+    let algorithms = args.verify.clone().unwrap_or(vec![args.algorithm]);
+
+    if args.delta == Some(0) {
+        eprintln!("Error: --delta must be positive.");
+        std::process::exit(1);
+    }
+
+    if args.delta.is_some() && !algorithms.contains(&Algorithm::DeltaStepping) {
+        eprintln!("Error: --delta only applies to --algo delta-stepping.");
+        std::process::exit(1);
+    }
+
+    if args.heap.is_some() && !algorithms.contains(&Algorithm::Dijkstra) {
+        eprintln!("Error: --heap only applies to --algo dijkstra.");
+        std::process::exit(1);
+    }
+
+    if args.common.undirected {
+        run::<Undirected>(args);
+    } else {
+        run::<Directed>(args);
+    }
+}
+
+fn run<Ty: EdgeType + Sync>(args: AppArgs) {
+    let (graph, throughput) = or_exit(read_gr_file::<Ty>(&args.common.gr_path));
+    eprintln!("Parsed {}.", throughput);
+
+    if let Some(algorithms) = &args.verify {
+        let solvers: Vec<_> = algorithms
+            .iter()
+            .map(|&algorithm| {
+                (
+                    algorithm,
+                    make_solver(algorithm, args.heap, args.delta, &graph),
+                )
+            })
+            .collect();
+        let queries: Vec<(usize, Option<usize>)> = match (&args.common.ss_path, &args.common.p2p_path) {
+            (Some(ss_path), _) => {
+                let ss_config = or_exit(parse_file(ss_path, parse_ss));
+                or_exit(ss_config.validate(graph.node_count()));
+                let sources = ss_config.sources.iter();
+                sources.map(|&source| (source, None)).collect()
+            }
+            (None, Some(p2p_path)) => {
+                let p2p_config = or_exit(parse_file(p2p_path, parse_p2p));
+                or_exit(p2p_config.validate(graph.node_count()));
+                let pairs = p2p_config.pairs.iter();
+                pairs
+                    .map(|&(source, target)| (source, Some(target)))
+                    .collect()
+            }
+            (None, None) => unreachable!("checked in main"),
+        };
+
+        let problems = verify(&graph, &solvers, &queries);
+        if problems > 0 {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let solver = make_solver(args.algorithm, args.heap, args.delta, &graph);
+
+    if let Some(ss_path) = &args.common.ss_path {
+        or_exit(run_ss(
+            &graph,
+            &args.common,
+            ss_path,
+            |start_node| solver.solve_all(&graph, start_node),
+            ShortestPathTree::checksum,
+        ));
+    } else if let Some(p2p_path) = &args.common.p2p_path {
+        let heuristic = args.co_path.map(|co_path| {
+            let co_config = or_exit(parse_file(&co_path, parse_co));
+            or_exit(co_config.validate(graph.node_count()));
+
+            let metric = if args.euclidean {
+                Metric::Euclidean
+            } else {
+                Metric::GreatCircle
+            };
+            Heuristic::new(&graph, &co_config.coordinates, metric)
+        });
+
+        let hierarchy = args.ch_path.map(|ch_path| {
+            let hierarchy = or_exit(read_file(&ch_path, ContractionHierarchy::read_from));
+            or_exit(hierarchy.validate(&graph));
+            hierarchy
+        });
+
+        let landmarks = args.alt_path.map(|alt_path| {
+            let landmarks = or_exit(read_file(&alt_path, Landmarks::read_from));
+            or_exit(landmarks.validate(&graph));
+            landmarks
+        });
+
+        let mut search = hierarchy.as_ref().map(|hierarchy| hierarchy.search());
+        let astar_settled = Cell::new((0, 0));
+
+        let query = |start_node, end_node: NodeIndex, route: bool| {
+            if let Some(search) = &mut search {
+                let path = search.query(start_node, end_node);
+                return Answer {
+                    distance: path.distance,
+                    settled: Some(path.settled),
+                    route: path.route,
+                };
+            }
+            let tree = match (&heuristic, &landmarks) {
+                (Some(heuristic), _) => {
+                    let tree = astar_single(&graph, start_node, end_node, heuristic);
+                    let (astar, dijkstra) = astar_settled.get();
+                    astar_settled.set((
+                        astar + tree.settled,
+                        dijkstra + dijkstra_single(&graph, start_node, end_node).settled,
+                    ));
+                    tree
+                }
+                (_, Some(landmarks)) => astar_single(&graph, start_node, end_node, landmarks),
+                _ if args.bidirectional => {
+                    bidirectional_dijkstra_single(&graph, start_node, end_node)
+                }
+                _ => solver.solve_single(&graph, start_node, end_node),
+            };
+            Answer {
+                distance: tree.distances[end_node.index()],
+                settled: Some(tree.settled),
+                route: if route { tree.path(end_node) } else { None },
+            }
+        };
+        let comments = || match heuristic {
+            Some(_) => {
+                let (astar, dijkstra) = astar_settled.get();
+                vec![format!("settled astar {} dijkstra {}", astar, dijkstra)]
+            }
+            None => Vec::new(),
+        };
+
+        or_exit(run_p2p(&graph, &args.common, p2p_path, query, comments));
+    }
+}
+
+#[derive(Debug)]
+struct AppArgs {
+    common: CommonArgs,
+    algorithm: Algorithm,
+    co_path: Option<PathBuf>,
+    ch_path: Option<PathBuf>,
+    alt_path: Option<PathBuf>,
+    delta: Option<u64>,
+    heap: Option<Heap>,
+    verify: Option<Vec<Algorithm>>,
+    euclidean: bool,
+    bidirectional: bool,
+}
+
+const HELP: &str = "\
+Lista 3 - shortest paths
+
+USAGE:
+  sp -d GR_PATH [OPTIONS]
+
+FLAGS:
+  -h, --help        Prints help information
+  --undirected      Treat arcs of the .gr file as undirected edges
+  --euclidean       Treat .co coordinates as points in the plane instead of
+                    longitude and latitude in millionths of a degree
+  --bidirectional   Answer -p2p queries with bidirectional Dijkstra
+
+OPTIONS:
+  -ss SS_PATH       Path to .ss file
+  -oss OSS_PATH     Path to output file
+  -p2p P2P_PATH     Path to .p2p file
+  -op2p OP2P_PATH   Path to output file
+  -paths PATHS_PATH Path to output file for the routes of -p2p queries
+  -co CO_PATH       Path to .co file; -p2p queries are then answered with A*
+  -ch CH_PATH       Path to hierarchy file from ch-prep; -p2p queries are then
+                    answered with Contraction Hierarchies
+  -alt ALT_PATH     Path to landmarks file from alt-prep; -p2p queries are then
+                    answered with ALT
+  -d GR_PATH        Path to .gr file
+  --algo ALGORITHM  dijkstra, dial, radix or delta-stepping; dijkstra, dial and
+                    radixheap are aliases of sp with a different default
+  --threads N       Also time the -ss sources on N threads in parallel
+                    (0 for one thread per core)
+  --delta DELTA     Bucket width of delta-stepping [default: mean arc cost]
+  --heap HEAP       Priority queue of dijkstra: binary (lazy deletion), 2-ary,
+                    4-ary, 8-ary (indexed d-ary heaps), pairing or fibonacci
+                    [default: binary]
+  --verify ALGOS    Instead of timing, run the comma-separated algorithms from
+                    the -ss sources or -p2p queries, and report on x lines where
+                    their distances differ from those of the first one or a
+                    tree fails the optimality conditions; exits with 1 if any do
+
+-ss results give the mean time per source in milliseconds on a t line and a
+d SOURCE CHECKSUM line per source, the sum of its finite distances modulo 2^62.
+-p2p results give all d SOURCE TARGET DISTANCE lines first, with distance inf
+for unreachable targets, then a c settled SOURCE TARGET COUNT line per query
+with the vertices it settled.
+
+";
+
+fn parse_args(default: Algorithm) -> Result<AppArgs, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+
+    // Help has a higher priority and should be handled separately.
+    if pargs.contains(["-h", "--help"]) {
+        print!("{}", HELP);
+        std::process::exit(0);
+    }
+
+    let args = AppArgs {
+        common: CommonArgs::parse(&mut pargs)?,
+        algorithm: pargs.opt_value_from_str("--algo")?.unwrap_or(default),
+        co_path: pargs.opt_value_from_os_str("-co", parse_path)?,
+        ch_path: pargs.opt_value_from_os_str("-ch", parse_path)?,
+        alt_path: pargs.opt_value_from_os_str("-alt", parse_path)?,
+        delta: pargs.opt_value_from_str("--delta")?,
+        heap: pargs.opt_value_from_str("--heap")?,
+        verify: pargs.opt_value_from_fn("--verify", parse_algorithms)?,
+        euclidean: pargs.contains("--euclidean"),
+        bidirectional: pargs.contains("--bidirectional"),
+    };
+
+    // It's up to the caller what to do with the remaining arguments.
+    let remaining = pargs.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unused arguments left: {:?}.", remaining);
+    }
+
+    Ok(args)
+}
+
+fn parse_algorithms(s: &str) -> Result<Vec<Algorithm>, String> {
+    s.split(',').map(str::parse).collect()
+}
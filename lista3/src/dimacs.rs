@@ -0,0 +1,330 @@
+// Streaming reader of DIMACS `.gr` files. Lines are read one at a time into a reused
+// buffer and parsed byte by byte, so the only full copy of the graph is the one built.
+// The small `.ss`, `.p2p` and `.co` files are still parsed with nom in `parsing`.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use petgraph::graph::NodeIndex;
+use petgraph::{EdgeType, Graph};
+
+use crate::binary::invalid_data;
+use crate::cache::{cache_path, CacheFile, Header};
+use crate::parsing::{ParseError, ParseErrorKind};
+
+// Receives the contents of a `.gr` file: the `p sp` line once, then every arc with
+// vertices already shifted to start at 0.
+trait ArcSink {
+    fn problem(&mut self, nodes: usize, arcs: usize) -> Result<(), ParseErrorKind>;
+    fn arc(&mut self, tail: usize, head: usize, cost: i64) -> Result<(), ParseErrorKind>;
+}
+
+fn parse_unsigned(field: &[u8]) -> Option<u64> {
+    if field.is_empty() {
+        return None;
+    }
+    let mut value: u64 = 0;
+    for &byte in field {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add((byte - b'0') as u64)?;
+    }
+    Some(value)
+}
+
+fn parse_signed(field: &[u8]) -> Option<i64> {
+    match field.split_first() {
+        Some((b'-', digits)) => 0i64.checked_sub_unsigned(parse_unsigned(digits)?),
+        _ => i64::try_from(parse_unsigned(field)?).ok(),
+    }
+}
+
+fn read_arcs<R: BufRead>(mut reader: R, sink: &mut impl ArcSink) -> Result<(), ParseError> {
+    const PROBLEM: ParseErrorKind = ParseErrorKind::Malformed("p sp NODES ARCS");
+    const ARC: ParseErrorKind = ParseErrorKind::Malformed("a TAIL HEAD COST");
+
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    let mut header = None;
+    let mut found = 0;
+
+    loop {
+        line.clear();
+        let error = |kind| ParseError {
+            line: line_number + 1,
+            kind,
+        };
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| error(ParseErrorKind::Io(e.to_string())))?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let error = |kind| ParseError {
+            line: line_number,
+            kind,
+        };
+
+        let mut fields = line
+            .split(|byte| byte.is_ascii_whitespace())
+            .filter(|field| !field.is_empty());
+        match (fields.next(), header) {
+            (None, _) => {}
+            (Some(field), _) if field[0] == b'c' => {}
+            (Some(b"p"), None) => {
+                let fields: Vec<&[u8]> = fields.collect();
+                let [b"sp", n, m] = fields[..] else {
+                    return Err(error(PROBLEM));
+                };
+                let (Some(n), Some(m)) = (parse_unsigned(n), parse_unsigned(m)) else {
+                    return Err(error(PROBLEM));
+                };
+                header = Some((n as usize, m as usize, line_number));
+                sink.problem(n as usize, m as usize).map_err(error)?;
+            }
+            (Some(b"p"), Some(_)) => return Err(error(ParseErrorKind::SecondProblem)),
+            (Some(b"a"), None) => {
+                return Err(error(ParseErrorKind::BeforeProblem("a".to_string())))
+            }
+            (Some(b"a"), Some((n, m, _))) => {
+                found += 1;
+                if found > m {
+                    return Err(error(ParseErrorKind::TooMany {
+                        items: "arcs",
+                        declared: m,
+                    }));
+                }
+                let fields: Vec<&[u8]> = fields.collect();
+                let [tail, head, cost] = fields[..] else {
+                    return Err(error(ARC));
+                };
+                let (Some(tail), Some(head), Some(cost)) = (
+                    parse_unsigned(tail),
+                    parse_unsigned(head),
+                    parse_signed(cost),
+                ) else {
+                    return Err(error(ARC));
+                };
+                for id in [tail, head] {
+                    if id == 0 || id > n as u64 {
+                        return Err(error(ParseErrorKind::VertexOutOfRange {
+                            vertex: id as usize,
+                            nodes: n,
+                        }));
+                    }
+                }
+                sink.arc(tail as usize - 1, head as usize - 1, cost)
+                    .map_err(error)?;
+            }
+            (Some(field), _) => {
+                return Err(error(ParseErrorKind::UnknownLine(
+                    String::from_utf8_lossy(field).into_owned(),
+                )))
+            }
+        }
+    }
+
+    match header {
+        None => Err(ParseError {
+            line: line_number + 1,
+            kind: ParseErrorKind::MissingProblem,
+        }),
+        Some((_, m, line)) if found < m => Err(ParseError {
+            line,
+            kind: ParseErrorKind::TooFew {
+                items: "arcs",
+                declared: m,
+                found,
+            },
+        }),
+        Some(_) => Ok(()),
+    }
+}
+
+// Vertices and arcs of petgraph graphs have 32-bit ids, so a problem line past that is
+// rejected before anything is allocated for it.
+fn check_size(nodes: usize, arcs: usize) -> Result<(), ParseErrorKind> {
+    if nodes > u32::MAX as usize {
+        return Err(ParseErrorKind::VertexLimit(nodes));
+    }
+    if arcs > u32::MAX as usize {
+        return Err(ParseErrorKind::ArcLimit);
+    }
+    Ok(())
+}
+
+fn non_negative(cost: i64) -> Result<u64, ParseErrorKind> {
+    u64::try_from(cost).map_err(|_| ParseErrorKind::NegativeCost(cost))
+}
+
+struct PetgraphSink<W, Ty: EdgeType, F> {
+    graph: Graph<(), W, Ty>,
+    weight: F,
+}
+
+impl<W, Ty: EdgeType, F> ArcSink for PetgraphSink<W, Ty, F>
+where
+    F: Fn(i64) -> Result<W, ParseErrorKind>,
+{
+    fn problem(&mut self, nodes: usize, arcs: usize) -> Result<(), ParseErrorKind> {
+        check_size(nodes, arcs)?;
+        self.graph = Graph::with_capacity(nodes, arcs);
+        for _ in 0..nodes {
+            self.graph.add_node(());
+        }
+        Ok(())
+    }
+
+    fn arc(&mut self, tail: usize, head: usize, cost: i64) -> Result<(), ParseErrorKind> {
+        let weight = (self.weight)(cost)?;
+        self.graph
+            .add_edge(NodeIndex::new(tail), NodeIndex::new(head), weight);
+        Ok(())
+    }
+}
+
+fn read_petgraph<W, Ty: EdgeType, R: BufRead>(
+    reader: R,
+    weight: impl Fn(i64) -> Result<W, ParseErrorKind>,
+) -> Result<Graph<(), W, Ty>, ParseError> {
+    let mut sink = PetgraphSink {
+        graph: Graph::with_capacity(0, 0),
+        weight,
+    };
+    read_arcs(reader, &mut sink)?;
+    Ok(sink.graph)
+}
+
+/// Reads a `.gr` file into a petgraph graph with the arcs in file order, without holding
+/// the text or a list of arcs in memory.
+pub fn read_gr<Ty: EdgeType, R: BufRead>(reader: R) -> Result<Graph<(), u64, Ty>, ParseError> {
+    read_petgraph(reader, non_negative)
+}
+
+/// Like [`read_gr`], but keeps negative arc costs.
+pub fn read_signed_gr<Ty: EdgeType, R: BufRead>(
+    reader: R,
+) -> Result<Graph<(), i64, Ty>, ParseError> {
+    read_petgraph(reader, Ok)
+}
+
+/// Size of a parsed file and the time it took.
+pub struct Throughput {
+    pub bytes: u64,
+    pub elapsed: Duration,
+    /// Whether the graph came from the binary cache instead of the `.gr` file.
+    pub cached: bool,
+}
+
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let megabytes = self.bytes as f64 / 1e6;
+        write!(
+            f,
+            "{:.1} MB in {} ms ({:.1} MB/s)",
+            megabytes,
+            self.elapsed.as_millis(),
+            megabytes / self.elapsed.as_secs_f64()
+        )?;
+        if self.cached {
+            write!(f, " from the binary cache")?;
+        }
+        Ok(())
+    }
+}
+
+fn read_file<T>(
+    path: &Path,
+    read: impl FnOnce(BufReader<File>) -> Result<T, ParseError>,
+) -> Result<(T, Throughput), String> {
+    let now = Instant::now();
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let bytes = file
+        .metadata()
+        .map_err(|e| format!("{}: {}", path.display(), e))?
+        .len();
+    let value = read(BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let throughput = Throughput {
+        bytes,
+        elapsed: now.elapsed(),
+        cached: false,
+    };
+    Ok((value, throughput))
+}
+
+// Loads the cache written by `gr2bin` next to `path` if it is fresh and `usable`. A cache
+// that cannot be used is reported, and the caller reads the `.gr` file instead.
+fn read_cache<W, Ty: EdgeType>(
+    path: &Path,
+    weight: impl Fn(u64) -> W,
+    usable: impl Fn(&Header) -> Result<(), &'static str>,
+) -> Option<(Graph<(), W, Ty>, Throughput)> {
+    let cache = cache_path(path);
+    if !cache.exists() {
+        return None;
+    }
+
+    let now = Instant::now();
+    let loaded = CacheFile::open(&cache).and_then(|file| {
+        usable(file.header()).map_err(invalid_data)?;
+        if !file.is_fresh_for(path)? {
+            return Ok(None);
+        }
+        let bytes = std::fs::metadata(&cache)?.len();
+        Ok(Some((file.read_graph(weight)?, bytes)))
+    });
+    match loaded {
+        Ok(Some((graph, bytes))) => {
+            let throughput = Throughput {
+                bytes,
+                elapsed: now.elapsed(),
+                cached: true,
+            };
+            Some((graph, throughput))
+        }
+        Ok(None) => {
+            eprintln!(
+                "Warning: {} is older than {}, run gr2bin again.",
+                cache.display(),
+                path.display()
+            );
+            None
+        }
+        Err(e) => {
+            eprintln!("Warning: ignoring {}: {}.", cache.display(), e);
+            None
+        }
+    }
+}
+
+/// Opens and reads a `.gr` file with [`read_gr`], measuring the parse throughput. Errors
+/// start with the path. A fresh binary cache (see [`crate::cache`]) is loaded instead.
+pub fn read_gr_file<Ty: EdgeType>(path: &Path) -> Result<(Graph<(), u64, Ty>, Throughput), String> {
+    if let Some(cached) = read_cache(path, |cost| cost, |_| Ok(())) {
+        return Ok(cached);
+    }
+    read_file(path, read_gr)
+}
+
+/// Opens and reads a `.gr` file with [`read_signed_gr`], measuring the parse throughput.
+pub fn read_signed_gr_file<Ty: EdgeType>(
+    path: &Path,
+) -> Result<(Graph<(), i64, Ty>, Throughput), String> {
+    let cached = read_cache(
+        path,
+        |cost| cost as i64,
+        |header| match header.max_cost <= i64::MAX as u64 {
+            true => Ok(()),
+            false => Err("arc costs do not fit in 64-bit signed integers"),
+        },
+    );
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+    read_file(path, read_signed_gr)
+}
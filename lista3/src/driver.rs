@@ -0,0 +1,234 @@
+// Query loops and result files shared by `sp` and `bellman`, generic over the distance type
+// of the graph so that both binaries write the same format.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use indicatif::ProgressBar;
+use petgraph::graph::NodeIndex;
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::Route;
+use crate::parallel::time_sources;
+use crate::parsing::{parse_file, parse_p2p, parse_ss};
+
+/// Arc costs and distances of the graphs the binaries solve.
+pub trait Distance: Copy + Ord + fmt::Display {
+    /// Distance of the vertices that cannot be reached.
+    const UNREACHABLE: Self;
+}
+
+impl Distance for u64 {
+    const UNREACHABLE: u64 = u64::MAX;
+}
+
+impl Distance for i64 {
+    const UNREACHABLE: i64 = i64::MAX;
+}
+
+// Shows unreachable distances as `inf`, whatever the distance type.
+struct Shown<D>(D);
+
+impl<D: Distance> fmt::Display for Shown<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == D::UNREACHABLE {
+            write!(f, "inf")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// Answer to one point-to-point query.
+pub struct Answer<D> {
+    pub distance: D,
+    /// Vertices settled by the search, for the searches that count them.
+    pub settled: Option<usize>,
+    /// Only asked for when the routes go to a `-paths` file.
+    pub route: Option<Route>,
+}
+
+/// Options shared by `sp` and `bellman`: the graph, the queries and where their results go.
+#[derive(Debug)]
+pub struct CommonArgs {
+    pub gr_path: PathBuf,
+    pub ss_path: Option<PathBuf>,
+    pub oss_path: Option<PathBuf>,
+    pub p2p_path: Option<PathBuf>,
+    pub op2p_path: Option<PathBuf>,
+    pub paths_path: Option<PathBuf>,
+    pub threads: Option<usize>,
+    pub undirected: bool,
+}
+
+impl CommonArgs {
+    pub fn parse(pargs: &mut pico_args::Arguments) -> Result<CommonArgs, pico_args::Error> {
+        Ok(CommonArgs {
+            gr_path: pargs.value_from_os_str("-d", parse_path)?,
+            ss_path: pargs.opt_value_from_os_str("-ss", parse_path)?,
+            oss_path: pargs.opt_value_from_os_str("-oss", parse_path)?,
+            p2p_path: pargs.opt_value_from_os_str("-p2p", parse_path)?,
+            op2p_path: pargs.opt_value_from_os_str("-op2p", parse_path)?,
+            paths_path: pargs.opt_value_from_os_str("-paths", parse_path)?,
+            threads: pargs.opt_value_from_str("--threads")?,
+            undirected: pargs.contains("--undirected"),
+        })
+    }
+}
+
+pub fn parse_path(s: &std::ffi::OsStr) -> Result<PathBuf, &'static str> {
+    Ok(s.into())
+}
+
+// Writes to the file at `path`, or to stdout if there is none, prefixing errors with the path.
+fn write_output(
+    path: Option<&Path>,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<(), String> {
+    let name = path.map_or("stdout".into(), |path| path.display().to_string());
+    let context = |e: io::Error| format!("{}: {}", name, e);
+    match path {
+        Some(path) => {
+            let mut file = BufWriter::new(File::create(path).map_err(context)?);
+            write(&mut file).and_then(|()| file.flush()).map_err(context)
+        }
+        None => write(&mut io::stdout().lock()).map_err(context),
+    }
+}
+
+// The `f` and `g` lines that open every result file. A graph without arcs has its cost
+// range written as `0 0`.
+fn write_header<D: Distance, Ty: EdgeType>(
+    out: &mut dyn Write,
+    graph: &Graph<(), D, Ty>,
+    gr_path: &Path,
+    queries_path: &Path,
+) -> io::Result<()> {
+    writeln!(out, "f {} {}", gr_path.display(), queries_path.display())?;
+    write!(out, "g {} {}", graph.node_count(), graph.edge_count())?;
+    match (graph.edge_weights().min(), graph.edge_weights().max()) {
+        (Some(min), Some(max)) => writeln!(out, " {} {}", min, max),
+        _ => writeln!(out, " 0 0"),
+    }
+}
+
+/// Runs `solve` from every source of the `-ss` file and writes the mean time of a run and
+/// the `checksum` of every tree. With `--threads`, the sources are then timed again in
+/// parallel.
+pub fn run_ss<D: Distance, Ty: EdgeType, T>(
+    graph: &Graph<(), D, Ty>,
+    args: &CommonArgs,
+    ss_path: &Path,
+    solve: impl Fn(NodeIndex) -> T + Sync,
+    checksum: impl Fn(&T) -> u64,
+) -> Result<(), String> {
+    let ss_config = parse_file(ss_path, parse_ss)?;
+    ss_config.validate(graph.node_count())?;
+
+    let mut times = Vec::with_capacity(ss_config.sources.len());
+    let mut checksums = Vec::with_capacity(ss_config.sources.len());
+
+    let bar = ProgressBar::new(ss_config.sources.len() as u64);
+
+    for &source in &ss_config.sources {
+        bar.inc(1);
+
+        let now = Instant::now();
+        let tree = solve(NodeIndex::new(source));
+        times.push(now.elapsed());
+        checksums.push(checksum(&tree));
+    }
+
+    bar.finish();
+
+    let batch = args.threads.map(|threads| {
+        let bar = ProgressBar::new(ss_config.sources.len() as u64);
+        let batch = time_sources(&ss_config.sources, threads, &bar, |start_node| {
+            solve(start_node);
+        });
+        bar.finish();
+        batch
+    });
+
+    let count = times.len() as f64;
+    let mean_time_millis = times.iter().map(|d| d.as_secs_f64() * 1e3).sum::<f64>() / count;
+
+    write_output(args.oss_path.as_deref(), |mut out| {
+        write_header(out, graph, &args.gr_path, ss_path)?;
+        writeln!(out, "t {:.3}", mean_time_millis)?;
+        for (source, checksum) in ss_config.sources.iter().zip(&checksums) {
+            writeln!(out, "d {} {}", source + 1, checksum)?;
+        }
+        if let Some(batch) = &batch {
+            batch.write_to(&mut out, &ss_config.sources)?;
+        }
+        Ok(())
+    })
+}
+
+/// Answers every pair of the `-p2p` file with `query`, which is told whether to find the
+/// route as well. Writes all distances, then the settled counts of the queries that have
+/// them and the `comments`, and the routes to the `-paths` file.
+pub fn run_p2p<D: Distance, Ty: EdgeType>(
+    graph: &Graph<(), D, Ty>,
+    args: &CommonArgs,
+    p2p_path: &Path,
+    mut query: impl FnMut(NodeIndex, NodeIndex, bool) -> Answer<D>,
+    comments: impl FnOnce() -> Vec<String>,
+) -> Result<(), String> {
+    let p2p_config = parse_file(p2p_path, parse_p2p)?;
+    p2p_config.validate(graph.node_count())?;
+
+    let bar = ProgressBar::new(p2p_config.pairs.len() as u64);
+
+    let mut answers = Vec::with_capacity(p2p_config.pairs.len());
+
+    for &(source, target) in &p2p_config.pairs {
+        bar.inc(1);
+
+        let start_node = NodeIndex::new(source);
+        let end_node = NodeIndex::new(target);
+        answers.push(query(start_node, end_node, args.paths_path.is_some()));
+    }
+
+    bar.finish();
+
+    let comments = comments();
+    write_output(args.op2p_path.as_deref(), |out| {
+        write_header(out, graph, &args.gr_path, p2p_path)?;
+        for (pair, answer) in p2p_config.pairs.iter().zip(&answers) {
+            let distance = Shown(answer.distance);
+            writeln!(out, "d {} {} {}", pair.0 + 1, pair.1 + 1, distance)?;
+        }
+        for (pair, answer) in p2p_config.pairs.iter().zip(&answers) {
+            if let Some(settled) = answer.settled {
+                writeln!(out, "c settled {} {} {}", pair.0 + 1, pair.1 + 1, settled)?;
+            }
+        }
+        for comment in comments.iter() {
+            writeln!(out, "c {}", comment)?;
+        }
+        Ok(())
+    })?;
+
+    if let Some(paths_path) = &args.paths_path {
+        write_output(Some(paths_path), |out| {
+            for (pair, answer) in p2p_config.pairs.iter().zip(&answers) {
+                let distance = Shown(answer.distance);
+                write!(out, "p {} {} {}", pair.0 + 1, pair.1 + 1, distance)?;
+                if let Some(route) = &answer.route {
+                    for node in route.nodes.iter() {
+                        write!(out, " {}", node.index() + 1)?;
+                    }
+                }
+                writeln!(out)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,522 @@
+use std::collections::BinaryHeap;
+
+use crate::algorithms::NoOrd;
+
+const NO_NODE: usize = usize::MAX;
+
+/// A min-priority queue of vertices `0..node_bound`, for searches like Dijkstra's that
+/// never queue a key smaller than the last one popped.
+///
+/// Queues with lazy deletion may pop a vertex again with an outdated key after its key
+/// was lowered; callers skip vertices they have already settled.
+pub trait MonotonePriorityQueue {
+    /// An empty queue for the vertices `0..node_bound`.
+    fn new(node_bound: usize) -> Self;
+
+    /// Queues `node` with `key`, or lowers its key to `key` if it is queued with a
+    /// larger one.
+    fn push(&mut self, node: usize, key: u64);
+
+    /// Removes a vertex with the smallest key and returns it with that key.
+    fn pop(&mut self) -> Option<(u64, usize)>;
+}
+
+/// `std::collections::BinaryHeap` with lazy deletion: lowering a key queues the vertex
+/// once more and the outdated entry stays behind.
+pub struct LazyBinaryHeap {
+    heap: BinaryHeap<NoOrd<u64, usize>>,
+}
+
+impl MonotonePriorityQueue for LazyBinaryHeap {
+    fn new(_node_bound: usize) -> Self {
+        LazyBinaryHeap {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn push(&mut self, node: usize, key: u64) {
+        self.heap.push(NoOrd(key, node));
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        self.heap.pop().map(|NoOrd(key, node)| (key, node))
+    }
+}
+
+/// Radix heap with lazy deletion: bucket `i` holds the keys whose highest bit differing
+/// from the last popped key is bit `i - 1`, and bucket 0 those equal to it.
+pub struct RadixHeap {
+    last: u64,
+    len: usize,
+    buckets: Vec<Vec<(u64, usize)>>,
+}
+
+impl RadixHeap {
+    fn bucket(&self, key: u64) -> usize {
+        (u64::BITS - (key ^ self.last).leading_zeros()) as usize
+    }
+}
+
+impl MonotonePriorityQueue for RadixHeap {
+    fn new(_node_bound: usize) -> Self {
+        RadixHeap {
+            last: 0,
+            len: 0,
+            buckets: (0..=u64::BITS).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    fn push(&mut self, node: usize, key: u64) {
+        debug_assert!(key >= self.last, "radix heap keys must be monotone");
+        let bucket = self.bucket(key);
+        self.buckets[bucket].push((key, node));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // Redistribute the first nonempty bucket around its minimum, which then lands in
+        // bucket 0 and every other key in a lower bucket than before.
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            let entries = std::mem::take(&mut self.buckets[i]);
+            self.last = entries.iter().map(|&(key, _)| key).min().unwrap();
+            for (key, node) in entries {
+                let bucket = self.bucket(key);
+                self.buckets[bucket].push((key, node));
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+/// Implicit `D`-ary heap with the position of every vertex, so keys are lowered in
+/// place (true decrease-key) and every vertex is queued at most once.
+pub struct DaryHeap<const D: usize> {
+    entries: Vec<(u64, usize)>,
+    position: Vec<usize>,
+}
+
+impl<const D: usize> DaryHeap<D> {
+    // Both sifts move a hole instead of swapping, writing the entry once at the end.
+    fn sift_up(&mut self, mut i: usize) {
+        let entry = self.entries[i];
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if entry.0 >= self.entries[parent].0 {
+                break;
+            }
+            self.entries[i] = self.entries[parent];
+            self.position[self.entries[i].1] = i;
+            i = parent;
+        }
+        self.entries[i] = entry;
+        self.position[entry.1] = i;
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let entry = self.entries[i];
+        loop {
+            let first = D * i + 1;
+            if first >= self.entries.len() {
+                break;
+            }
+            let last = (first + D).min(self.entries.len());
+            let child = (first..last).min_by_key(|&c| self.entries[c].0).unwrap();
+            if self.entries[child].0 >= entry.0 {
+                break;
+            }
+            self.entries[i] = self.entries[child];
+            self.position[self.entries[i].1] = i;
+            i = child;
+        }
+        self.entries[i] = entry;
+        self.position[entry.1] = i;
+    }
+}
+
+impl<const D: usize> MonotonePriorityQueue for DaryHeap<D> {
+    fn new(node_bound: usize) -> Self {
+        DaryHeap {
+            entries: Vec::new(),
+            position: vec![NO_NODE; node_bound],
+        }
+    }
+
+    fn push(&mut self, node: usize, key: u64) {
+        match self.position[node] {
+            NO_NODE => {
+                self.entries.push((key, node));
+                self.sift_up(self.entries.len() - 1);
+            }
+            i if key < self.entries[i].0 => {
+                self.entries[i].0 = key;
+                self.sift_up(i);
+            }
+            _ => {}
+        }
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let top = self.entries.swap_remove(0);
+        self.position[top.1] = NO_NODE;
+        if !self.entries.is_empty() {
+            self.sift_down(0);
+        }
+        Some(top)
+    }
+}
+
+#[derive(Clone)]
+struct PairingNode {
+    key: u64,
+    child: usize,
+    sibling: usize,
+    // Parent for the first child of a vertex, the left sibling for the others.
+    prev: usize,
+    queued: bool,
+}
+
+/// Pairing heap over an arena indexed by vertex, with two-pass pairing on pop and
+/// decrease-key by cutting the subtree and melding it with the root.
+pub struct PairingHeap {
+    nodes: Vec<PairingNode>,
+    root: usize,
+    pairs: Vec<usize>,
+}
+
+impl PairingHeap {
+    // Melds two trees given by their roots and returns the root of the result.
+    fn meld(&mut self, a: usize, b: usize) -> usize {
+        if a == NO_NODE {
+            return b;
+        }
+        if b == NO_NODE {
+            return a;
+        }
+        let (parent, child) = if self.nodes[b].key < self.nodes[a].key {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = self.nodes[parent].child;
+        if first != NO_NODE {
+            self.nodes[first].prev = child;
+        }
+        self.nodes[child].sibling = first;
+        self.nodes[child].prev = parent;
+        self.nodes[parent].child = child;
+        parent
+    }
+
+    // Detaches the subtree of `node` from its parent and siblings.
+    fn cut(&mut self, node: usize) {
+        let PairingNode { prev, sibling, .. } = self.nodes[node];
+        if self.nodes[prev].child == node {
+            self.nodes[prev].child = sibling;
+        } else {
+            self.nodes[prev].sibling = sibling;
+        }
+        if sibling != NO_NODE {
+            self.nodes[sibling].prev = prev;
+        }
+        self.nodes[node].prev = NO_NODE;
+        self.nodes[node].sibling = NO_NODE;
+    }
+}
+
+impl MonotonePriorityQueue for PairingHeap {
+    fn new(node_bound: usize) -> Self {
+        let empty = PairingNode {
+            key: 0,
+            child: NO_NODE,
+            sibling: NO_NODE,
+            prev: NO_NODE,
+            queued: false,
+        };
+        PairingHeap {
+            nodes: vec![empty; node_bound],
+            root: NO_NODE,
+            pairs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, node: usize, key: u64) {
+        if !self.nodes[node].queued {
+            self.nodes[node] = PairingNode {
+                key,
+                child: NO_NODE,
+                sibling: NO_NODE,
+                prev: NO_NODE,
+                queued: true,
+            };
+            self.root = self.meld(self.root, node);
+        } else if key < self.nodes[node].key {
+            self.nodes[node].key = key;
+            if node != self.root {
+                self.cut(node);
+                self.root = self.meld(self.root, node);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        let top = self.root;
+        if top == NO_NODE {
+            return None;
+        }
+        self.nodes[top].queued = false;
+
+        // Meld the children in pairs from left to right, then the pairs from right to left.
+        let mut pairs = std::mem::take(&mut self.pairs);
+        let mut child = self.nodes[top].child;
+        while child != NO_NODE {
+            let first = child;
+            let second = self.nodes[first].sibling;
+            child = if second == NO_NODE {
+                NO_NODE
+            } else {
+                self.nodes[second].sibling
+            };
+            for node in [first, second] {
+                if node != NO_NODE {
+                    self.nodes[node].prev = NO_NODE;
+                    self.nodes[node].sibling = NO_NODE;
+                }
+            }
+            pairs.push(self.meld(first, second));
+        }
+        let mut root = NO_NODE;
+        while let Some(pair) = pairs.pop() {
+            root = self.meld(pair, root);
+        }
+        self.pairs = pairs;
+        self.root = root;
+
+        Some((self.nodes[top].key, top))
+    }
+}
+
+#[derive(Clone)]
+struct FibonacciNode {
+    key: u64,
+    parent: usize,
+    child: usize,
+    // Neighbours in the circular list of roots or of the children of `parent`.
+    left: usize,
+    right: usize,
+    degree: usize,
+    marked: bool,
+    queued: bool,
+}
+
+/// Fibonacci heap over an arena indexed by vertex: O(1) amortized push and decrease-key
+/// through cascading cuts, with the roots consolidated by degree on pop.
+pub struct FibonacciHeap {
+    nodes: Vec<FibonacciNode>,
+    min: usize,
+    roots: Vec<usize>,
+    by_degree: Vec<usize>,
+}
+
+impl FibonacciHeap {
+    // Adds a lone `node` to the root list.
+    fn add_root(&mut self, node: usize) {
+        self.nodes[node].parent = NO_NODE;
+        if self.min == NO_NODE {
+            self.nodes[node].left = node;
+            self.nodes[node].right = node;
+            self.min = node;
+            return;
+        }
+        let right = self.nodes[self.min].right;
+        self.nodes[node].left = self.min;
+        self.nodes[node].right = right;
+        self.nodes[self.min].right = node;
+        self.nodes[right].left = node;
+        if self.nodes[node].key < self.nodes[self.min].key {
+            self.min = node;
+        }
+    }
+
+    // Collects the circular list starting at `first` into `self.roots`.
+    fn collect_list(&mut self, first: usize) {
+        if first == NO_NODE {
+            return;
+        }
+        let mut node = first;
+        loop {
+            self.roots.push(node);
+            node = self.nodes[node].right;
+            if node == first {
+                break;
+            }
+        }
+    }
+
+    // Moves `node` from the children of `parent` to the root list.
+    fn cut(&mut self, node: usize, parent: usize) {
+        let FibonacciNode { left, right, .. } = self.nodes[node];
+        if self.nodes[parent].child == node {
+            self.nodes[parent].child = if right == node { NO_NODE } else { right };
+        }
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[parent].degree -= 1;
+        self.nodes[node].marked = false;
+        self.add_root(node);
+    }
+
+    fn cascading_cut(&mut self, mut node: usize) {
+        loop {
+            let parent = self.nodes[node].parent;
+            if parent == NO_NODE {
+                break;
+            }
+            if !self.nodes[node].marked {
+                self.nodes[node].marked = true;
+                break;
+            }
+            self.cut(node, parent);
+            node = parent;
+        }
+    }
+
+    // Makes the root `child` a child of the root `parent`.
+    fn link(&mut self, child: usize, parent: usize) {
+        self.nodes[child].parent = parent;
+        self.nodes[child].marked = false;
+        let first = self.nodes[parent].child;
+        if first == NO_NODE {
+            self.nodes[child].left = child;
+            self.nodes[child].right = child;
+            self.nodes[parent].child = child;
+        } else {
+            let right = self.nodes[first].right;
+            self.nodes[child].left = first;
+            self.nodes[child].right = right;
+            self.nodes[first].right = child;
+            self.nodes[right].left = child;
+        }
+        self.nodes[parent].degree += 1;
+    }
+
+    // Links the trees in `self.roots` until no two roots have the same degree and
+    // rebuilds the root list from them.
+    fn consolidate(&mut self) {
+        let roots = std::mem::take(&mut self.roots);
+        for &root in roots.iter() {
+            let mut node = root;
+            let mut degree = self.nodes[node].degree;
+            loop {
+                if degree >= self.by_degree.len() {
+                    self.by_degree.resize(degree + 1, NO_NODE);
+                }
+                let other = self.by_degree[degree];
+                if other == NO_NODE {
+                    break;
+                }
+                self.by_degree[degree] = NO_NODE;
+                let (parent, child) = if self.nodes[other].key < self.nodes[node].key {
+                    (other, node)
+                } else {
+                    (node, other)
+                };
+                self.link(child, parent);
+                node = parent;
+                degree += 1;
+            }
+            self.by_degree[degree] = node;
+        }
+        self.roots = roots;
+        self.roots.clear();
+
+        self.min = NO_NODE;
+        for degree in 0..self.by_degree.len() {
+            let node = std::mem::replace(&mut self.by_degree[degree], NO_NODE);
+            if node != NO_NODE {
+                self.add_root(node);
+            }
+        }
+    }
+}
+
+impl MonotonePriorityQueue for FibonacciHeap {
+    fn new(node_bound: usize) -> Self {
+        let empty = FibonacciNode {
+            key: 0,
+            parent: NO_NODE,
+            child: NO_NODE,
+            left: NO_NODE,
+            right: NO_NODE,
+            degree: 0,
+            marked: false,
+            queued: false,
+        };
+        FibonacciHeap {
+            nodes: vec![empty; node_bound],
+            min: NO_NODE,
+            roots: Vec::new(),
+            by_degree: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, node: usize, key: u64) {
+        if !self.nodes[node].queued {
+            self.nodes[node] = FibonacciNode {
+                key,
+                parent: NO_NODE,
+                child: NO_NODE,
+                left: NO_NODE,
+                right: NO_NODE,
+                degree: 0,
+                marked: false,
+                queued: true,
+            };
+            self.add_root(node);
+        } else if key < self.nodes[node].key {
+            self.nodes[node].key = key;
+            let parent = self.nodes[node].parent;
+            if parent != NO_NODE && key < self.nodes[parent].key {
+                self.cut(node, parent);
+                self.cascading_cut(parent);
+            } else if key < self.nodes[self.min].key {
+                self.min = node;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(u64, usize)> {
+        let top = self.min;
+        if top == NO_NODE {
+            return None;
+        }
+        self.nodes[top].queued = false;
+
+        // The other roots and the children of `top` become the new roots.
+        let first_root = self.nodes[top].right;
+        if first_root != top {
+            let last_root = self.nodes[top].left;
+            self.nodes[last_root].right = first_root;
+            self.nodes[first_root].left = last_root;
+            self.collect_list(first_root);
+        }
+        self.collect_list(self.nodes[top].child);
+        for i in 0..self.roots.len() {
+            let root = self.roots[i];
+            self.nodes[root].parent = NO_NODE;
+        }
+        self.min = NO_NODE;
+        self.consolidate();
+
+        Some((self.nodes[top].key, top))
+    }
+}
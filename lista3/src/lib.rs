@@ -1,2 +1,15 @@
 pub mod algorithms;
+pub mod alt;
+pub mod astar;
+mod binary;
+pub mod cache;
+pub mod ch;
+pub mod cli;
+pub mod dimacs;
+pub mod driver;
+pub mod heap;
+pub mod negative;
+pub mod parallel;
 pub mod parsing;
+pub mod solver;
+pub mod verify;
@@ -0,0 +1,321 @@
+use std::collections::VecDeque;
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeRef, NodeIndexable};
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::{Route, CHECKSUM_MASK};
+
+/// Distances from a single source in a graph with possibly negative arc costs.
+///
+/// Unreachable vertices have distance `i64::MAX` and no predecessor.
+pub struct SignedShortestPathTree {
+    pub distances: Vec<i64>,
+    pub predecessors: Vec<Option<(NodeIndex, EdgeIndex)>>,
+    /// Number of vertex scans (passes over the outgoing arcs of a vertex) performed.
+    pub scans: usize,
+}
+
+/// A cycle of negative total cost: arc `edges[i]` leads from `nodes[i]` to
+/// `nodes[(i + 1) % nodes.len()]`.
+#[derive(Debug)]
+pub struct NegativeCycle {
+    pub nodes: Vec<NodeIndex>,
+    pub edges: Vec<EdgeIndex>,
+}
+
+/// Why a signed solver returned no shortest path tree.
+#[derive(Debug)]
+pub enum SignedError {
+    /// A negative cycle is reachable from the source.
+    NegativeCycle(NegativeCycle),
+    /// The distance of `node` went past the range of `i64`.
+    Overflow { node: NodeIndex },
+}
+
+impl SignedShortestPathTree {
+    fn new(node_bound: usize, start: NodeIndex) -> SignedShortestPathTree {
+        let mut distances = vec![i64::MAX; node_bound];
+        distances[start.index()] = 0;
+        SignedShortestPathTree {
+            distances,
+            predecessors: vec![None; node_bound],
+            scans: 0,
+        }
+    }
+
+    // Relaxes the outgoing arcs of `node`, calling `improved` for every vertex whose
+    // distance went down.
+    fn scan<Ty: EdgeType>(
+        &mut self,
+        graph: &Graph<(), i64, Ty>,
+        node: NodeIndex,
+        mut improved: impl FnMut(NodeIndex),
+    ) -> Result<(), SignedError> {
+        self.scans += 1;
+        let score = self.distances[node.index()];
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            let next_score = score
+                .checked_add(*edge.weight())
+                .filter(|&next_score| next_score != i64::MAX)
+                .ok_or(SignedError::Overflow { node: next })?;
+            if next_score < self.distances[next.index()] {
+                self.distances[next.index()] = next_score;
+                self.predecessors[next.index()] = Some((node, edge.id()));
+                improved(next);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the vertices and arcs on the tree path from the source to `target`.
+    pub fn path(&self, target: NodeIndex) -> Option<Route> {
+        if self.distances[target.index()] == i64::MAX {
+            return None;
+        }
+
+        let mut nodes = vec![target];
+        let mut edges = Vec::new();
+        let mut current = target;
+        while let Some((previous, edge)) = self.predecessors[current.index()] {
+            nodes.push(previous);
+            edges.push(edge);
+            current = previous;
+        }
+        nodes.reverse();
+        edges.reverse();
+
+        Some(Route { nodes, edges })
+    }
+
+    /// Like [`crate::algorithms::ShortestPathTree::checksum`]; negative distances count
+    /// modulo 2^62 as well.
+    pub fn checksum(&self) -> u64 {
+        let sum = self
+            .distances
+            .iter()
+            .filter(|&&distance| distance != i64::MAX)
+            .fold(0u64, |sum, &distance| sum.wrapping_add(distance as u64));
+        sum & CHECKSUM_MASK
+    }
+
+    // Any cycle of the predecessor graph has negative cost.
+    fn predecessor_cycle(&self) -> Option<NegativeCycle> {
+        const NEW: u8 = 0;
+        const ON_WALK: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![NEW; self.predecessors.len()];
+        for start in 0..self.predecessors.len() {
+            let mut walk = Vec::new();
+            let mut current = start;
+            while state[current] == NEW {
+                state[current] = ON_WALK;
+                walk.push(current);
+                match self.predecessors[current] {
+                    Some((previous, _)) => current = previous.index(),
+                    None => break,
+                }
+            }
+
+            if state[current] == ON_WALK && self.predecessors[current].is_some() {
+                // `current` is on a cycle; follow it backwards once more to list it.
+                let mut nodes = Vec::new();
+                let mut edges = Vec::new();
+                let mut node = current;
+                loop {
+                    let (previous, edge) = self.predecessors[node].unwrap();
+                    nodes.push(previous);
+                    edges.push(edge);
+                    node = previous.index();
+                    if node == current {
+                        break;
+                    }
+                }
+                nodes.reverse();
+                edges.reverse();
+                return Some(NegativeCycle { nodes, edges });
+            }
+
+            for node in walk {
+                state[node] = DONE;
+            }
+        }
+        None
+    }
+}
+
+/// Bellman–Ford: rounds relaxing every arc, stopping once a round changes nothing.
+/// Changes after round `n - 1` mean a negative cycle is reachable, and it shows up in
+/// the predecessor graph.
+pub fn bellman_ford<Ty: EdgeType>(
+    graph: &Graph<(), i64, Ty>,
+    start: NodeIndex,
+) -> Result<SignedShortestPathTree, SignedError> {
+    let mut tree = SignedShortestPathTree::new(graph.node_bound(), start);
+
+    for round in 1.. {
+        let mut changed = false;
+        for node in graph.node_indices() {
+            if tree.distances[node.index()] != i64::MAX {
+                tree.scan(graph, node, |_| changed = true)?;
+            }
+        }
+        if !changed {
+            break;
+        }
+        if round >= graph.node_count() {
+            if let Some(cycle) = tree.predecessor_cycle() {
+                return Err(SignedError::NegativeCycle(cycle));
+            }
+        }
+    }
+    Ok(tree)
+}
+
+/// Queue-based Bellman–Ford (SPFA): only vertices whose distance went down since their
+/// last scan are scanned again. The predecessor graph is checked for a cycle after
+/// every `n` scans, which keeps the check amortized O(1) per scan.
+pub fn spfa<Ty: EdgeType>(
+    graph: &Graph<(), i64, Ty>,
+    start: NodeIndex,
+) -> Result<SignedShortestPathTree, SignedError> {
+    let mut tree = SignedShortestPathTree::new(graph.node_bound(), start);
+    let mut queue = VecDeque::from([start]);
+    let mut in_queue = vec![false; graph.node_bound()];
+    in_queue[start.index()] = true;
+
+    while let Some(node) = queue.pop_front() {
+        in_queue[node.index()] = false;
+        tree.scan(graph, node, |next| {
+            if !in_queue[next.index()] {
+                in_queue[next.index()] = true;
+                queue.push_back(next);
+            }
+        })?;
+
+        if tree.scans.is_multiple_of(graph.node_count()) {
+            if let Some(cycle) = tree.predecessor_cycle() {
+                return Err(SignedError::NegativeCycle(cycle));
+            }
+        }
+    }
+    Ok(tree)
+}
+
+// Reduced cost of an arc, widened so that it cannot overflow; arcs into unreached
+// vertices count as negative and arcs out of them as positive, so unreached vertices are
+// only ever leaves of a pass.
+fn reduced_cost(tree: &SignedShortestPathTree, from: NodeIndex, cost: i64, to: NodeIndex) -> i128 {
+    match (tree.distances[from.index()], tree.distances[to.index()]) {
+        (i64::MAX, _) => i128::MAX,
+        (_, i64::MAX) => i128::MIN,
+        (from, to) => from as i128 + cost as i128 - to as i128,
+    }
+}
+
+/// Goldberg–Radzik: every pass takes the vertices improved since their last scan that
+/// have an arc of negative reduced cost, finds everything reachable from them over arcs
+/// of non-positive reduced cost, and scans that set in topological order. A cycle of
+/// such arcs with negative total cost is reported as soon as the search closes it.
+pub fn goldberg_radzik<Ty: EdgeType>(
+    graph: &Graph<(), i64, Ty>,
+    start: NodeIndex,
+) -> Result<SignedShortestPathTree, SignedError> {
+    const NEW: u8 = 0;
+    const ON_STACK: u8 = 1;
+    const DONE: u8 = 2;
+
+    let n = graph.node_bound();
+    let mut tree = SignedShortestPathTree::new(n, start);
+    let mut labeled = vec![start];
+    let mut is_labeled = vec![false; n];
+    is_labeled[start.index()] = true;
+    let mut state = vec![NEW; n];
+    let mut passes = 0;
+
+    while !labeled.is_empty() {
+        passes += 1;
+        // Without negative cycles there are at most `n` passes; past that, the
+        // predecessor graph is bound to contain one eventually.
+        if passes > graph.node_count() {
+            if let Some(cycle) = tree.predecessor_cycle() {
+                return Err(SignedError::NegativeCycle(cycle));
+            }
+        }
+
+        let mut roots = Vec::new();
+        for node in labeled.drain(..) {
+            is_labeled[node.index()] = false;
+            if graph
+                .edges(node)
+                .any(|edge| reduced_cost(&tree, node, *edge.weight(), edge.target()) < 0)
+            {
+                roots.push(node);
+            }
+        }
+
+        // Depth-first search over admissible arcs, collecting vertices in post-order.
+        // `path` holds the vertices on the stack with the arcs used to enter them.
+        let mut order = Vec::new();
+        for root in roots {
+            if state[root.index()] != NEW {
+                continue;
+            }
+            state[root.index()] = ON_STACK;
+            let mut stack = vec![graph.edges(root)];
+            let mut path = vec![(root, None)];
+            while let Some(edges) = stack.last_mut() {
+                let node = path.last().unwrap().0;
+                let Some(edge) = edges.next() else {
+                    state[node.index()] = DONE;
+                    order.push(node);
+                    stack.pop();
+                    path.pop();
+                    continue;
+                };
+                let next = edge.target();
+                if reduced_cost(&tree, node, *edge.weight(), next) > 0 {
+                    continue;
+                }
+                match state[next.index()] {
+                    NEW => {
+                        state[next.index()] = ON_STACK;
+                        stack.push(graph.edges(next));
+                        path.push((next, Some(edge.id())));
+                    }
+                    ON_STACK => {
+                        let i = path.iter().position(|&(on_path, _)| on_path == next);
+                        let cycle = &path[i.unwrap()..];
+                        let edges: Vec<EdgeIndex> = cycle
+                            .iter()
+                            .skip(1)
+                            .map(|&(_, arc)| arc.unwrap())
+                            .chain([edge.id()])
+                            .collect();
+                        // Zero-cost cycles are fine, the order is then only approximate.
+                        let cost: i128 = edges.iter().map(|&arc| graph[arc] as i128).sum();
+                        if cost < 0 {
+                            let nodes = cycle.iter().map(|&(node, _)| node).collect();
+                            return Err(SignedError::NegativeCycle(NegativeCycle { nodes, edges }));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Reverse post-order is a topological order of the admissible arcs.
+        for &node in order.iter().rev() {
+            state[node.index()] = NEW;
+            tree.scan(graph, node, |next| {
+                if !is_labeled[next.index()] {
+                    is_labeled[next.index()] = true;
+                    labeled.push(next);
+                }
+            })?;
+        }
+    }
+    Ok(tree)
+}
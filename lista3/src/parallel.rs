@@ -0,0 +1,80 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use indicatif::ProgressBar;
+use petgraph::graph::NodeIndex;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+/// Timing of a batch of independent single-source computations.
+pub struct BatchTimes {
+    /// Number of threads of the pool, which is one per core when 0 were asked for.
+    pub threads: usize,
+    /// Wall time of the run from `sources[i]`, in the order of the sources.
+    pub times: Vec<Duration>,
+    /// Wall time of the whole batch.
+    pub wall: Duration,
+}
+
+impl BatchTimes {
+    /// Sources finished per second of wall time.
+    pub fn throughput(&self) -> f64 {
+        self.times.len() as f64 / self.wall.as_secs_f64()
+    }
+
+    /// Writes the batch as comment lines of a `.ss.res` file: one with the thread count,
+    /// wall time and throughput, then one per source with the time of its run. Times are in
+    /// milliseconds with microsecond precision.
+    pub fn write_to(&self, writer: &mut impl Write, sources: &[usize]) -> io::Result<()> {
+        writeln!(
+            writer,
+            "c threads {} wall {:.3} throughput {}",
+            self.threads,
+            self.wall.as_secs_f64() * 1e3,
+            self.throughput()
+        )?;
+        for (source, time) in sources.iter().zip(&self.times) {
+            writeln!(
+                writer,
+                "c time {} {:.3}",
+                source + 1,
+                time.as_secs_f64() * 1e3
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `solve` from every source on a pool of `threads` threads, which share everything
+/// `solve` borrows, such as the graph.
+pub fn time_sources(
+    sources: &[usize],
+    threads: usize,
+    bar: &ProgressBar,
+    solve: impl Fn(NodeIndex) + Sync,
+) -> BatchTimes {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap();
+
+    let now = Instant::now();
+    let times = pool.install(|| {
+        sources
+            .par_iter()
+            .map(|&source| {
+                let start = Instant::now();
+                solve(NodeIndex::new(source));
+                let elapsed = start.elapsed();
+                bar.inc(1);
+                elapsed
+            })
+            .collect()
+    });
+
+    BatchTimes {
+        threads: pool.current_num_threads(),
+        times,
+        wall: now.elapsed(),
+    }
+}
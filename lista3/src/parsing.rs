@@ -1,19 +1,16 @@
-use std::collections::HashMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::Cursor;
+use std::path::Path;
 
 use nom::{
-    bytes::complete::tag, character::complete::digit1, character::complete::line_ending,
-    character::complete::multispace1, combinator::map_res, combinator::opt, IResult,
-    sequence::tuple,
+    bytes::complete::tag, character::complete::digit1, character::complete::i64 as signed,
+    character::complete::space1, combinator::all_consuming, combinator::map_res, sequence::tuple,
+    IResult,
 };
-use petgraph::Graph;
-use petgraph::Undirected;
+use petgraph::{EdgeType, Graph};
 
-#[derive(Debug)]
-struct GraphEdge {
-    source: usize,
-    target: usize,
-    weight: u64,
-}
+use crate::dimacs::{read_gr, read_signed_gr};
 
 #[derive(Debug)]
 pub struct ProblemSpecSS {
@@ -21,209 +18,380 @@ pub struct ProblemSpecSS {
     pub sources: Vec<usize>,
 }
 
+#[derive(Debug)]
 pub struct ProblemSpecP2P {
     pub num_pairs: usize,
     pub pairs: Vec<(usize, usize)>,
 }
 
-fn parse_comment(input: &str) -> IResult<&str, ()> {
-    let (input, _) = tuple((
-        tag("c"),
-        opt(nom::bytes::complete::is_not("\n\r")),
-        line_ending,
-    ))(input)?;
-    Ok((input, ()))
-}
-
-fn parse_problem_gr(input: &str) -> IResult<&str, (usize, usize)> {
-    let (input, (_, num_nodes, _, num_edges, _)) = tuple((
-        tag("p sp "),
-        map_res(digit1, str::parse::<usize>),
-        multispace1,
-        map_res(digit1, str::parse::<usize>),
-        line_ending,
-    ))(input)?;
-    Ok((input, (num_nodes, num_edges)))
-}
-
-fn parse_problem_ss(input: &str) -> IResult<&str, usize> {
-    let (input, (_, num_sources, _)) = tuple((
-        tag("p aux sp ss "),
-        map_res(digit1, str::parse::<usize>),
-        line_ending,
-    ))(input)?;
-    Ok((input, num_sources))
-}
-
-fn parse_source(input: &str) -> IResult<&str, usize> {
-    let (input, (_, source, _)) =
-        tuple((tag("s "), map_res(digit1, str::parse::<usize>), line_ending))(input)?;
-    Ok((input, source))
-}
-
-fn parse_edge(input: &str) -> IResult<&str, GraphEdge> {
-    let (input, (_, _, source, _, target, _, weight, _)) = tuple((
-        tag("a"),
-        multispace1,
-        map_res(digit1, str::parse::<usize>),
-        multispace1,
-        map_res(digit1, str::parse::<usize>),
-        multispace1,
-        map_res(digit1, str::parse::<u64>),
-        line_ending,
-    ))(input)?;
-    Ok((
-        input,
-        GraphEdge {
-            source,
-            target,
-            weight,
-        },
-    ))
+/// Vertex coordinates from a `.co` file, indexed by vertex id minus one.
+#[derive(Debug)]
+pub struct ProblemSpecCO {
+    pub num_nodes: usize,
+    pub coordinates: Vec<(i64, i64)>,
 }
 
-fn parse_dimacs_gr(input: &str) -> IResult<&str, (usize, usize, Vec<GraphEdge>)> {
-    let mut remaining_input = input;
-    // remove all comments from the be of the file
-    while let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-        remaining_input = input;
-    }
+/// An error in a DIMACS file, with the line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number; one past the last line for errors found at the end of the file.
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
 
-    let (mut remaining_input, (num_nodes, num_edges)) = parse_problem_gr(remaining_input)?;
-    let mut edges = Vec::with_capacity(num_edges);
-
-    loop {
-        if let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-            remaining_input = input;
-            continue;
-        } else {
-            match parse_edge(remaining_input) {
-                Ok((input, edge)) => {
-                    remaining_input = input;
-                    edges.push(edge);
-                }
-                Err(_) => {
-                    break;
-                }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The line does not have the given form, e.g. `a TAIL HEAD COST`.
+    Malformed(&'static str),
+    /// The line starts with something other than `c`, `p` or the item letter of the format.
+    UnknownLine(String),
+    /// A line with the given item letter comes before the problem line.
+    BeforeProblem(String),
+    SecondProblem,
+    MissingProblem,
+    VertexOutOfRange {
+        vertex: usize,
+        nodes: usize,
+    },
+    ZeroVertex,
+    /// A second `v` line for the same vertex of a `.co` file.
+    DuplicateVertex(usize),
+    NegativeCost(i64),
+    /// More items, e.g. `arcs`, than the problem line declares.
+    TooMany {
+        items: &'static str,
+        declared: usize,
+    },
+    /// Fewer items than the problem line declares; reported on the problem line.
+    TooFew {
+        items: &'static str,
+        declared: usize,
+        found: usize,
+    },
+    /// The graph has too many vertices for 32-bit ids.
+    VertexLimit(usize),
+    /// The graph has too many arcs for 32-bit ids.
+    ArcLimit,
+    Io(String),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Malformed(form) => write!(f, "expected '{}'", form),
+            ParseErrorKind::UnknownLine(first) => write!(f, "unknown line type '{}'", first),
+            ParseErrorKind::BeforeProblem(first) => {
+                write!(f, "'{}' line before the problem line", first)
+            }
+            ParseErrorKind::SecondProblem => write!(f, "second problem line"),
+            ParseErrorKind::MissingProblem => write!(f, "missing problem line"),
+            ParseErrorKind::VertexOutOfRange { vertex, nodes } => {
+                write!(f, "vertex {} is outside of 1..={}", vertex, nodes)
+            }
+            ParseErrorKind::ZeroVertex => write!(f, "vertex ids start at 1"),
+            ParseErrorKind::DuplicateVertex(vertex) => {
+                write!(f, "second line for vertex {}", vertex)
             }
+            ParseErrorKind::NegativeCost(cost) => write!(f, "negative arc cost {}", cost),
+            ParseErrorKind::TooMany { items, declared } => write!(
+                f,
+                "more {} than the {} declared on the problem line",
+                items, declared
+            ),
+            ParseErrorKind::TooFew {
+                items,
+                declared,
+                found,
+            } => write!(
+                f,
+                "the problem line declares {} {}, but the file has {}",
+                declared, items, found
+            ),
+            ParseErrorKind::VertexLimit(nodes) => {
+                write!(f, "{} vertices do not fit in 32-bit ids", nodes)
+            }
+            ParseErrorKind::ArcLimit => write!(f, "more arcs than fit in 32-bit ids"),
+            ParseErrorKind::Io(message) => write!(f, "{}", message),
         }
     }
-
-    Ok((remaining_input, (num_nodes, num_edges, edges)))
 }
 
-pub fn parse_ss(input: &str) -> IResult<&str, ProblemSpecSS> {
-    let mut remaining_input = input;
-    // remove all comments from the be of the file
-    while let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-        remaining_input = input;
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
     }
+}
+
+impl std::error::Error for ParseError {}
 
-    let (mut remaining_input, num_sources) = parse_problem_ss(remaining_input)?;
+impl ProblemSpecSS {
+    /// Checks that there is at least one source, since the results give the mean time of
+    /// a run, and that every source is a vertex of the graph.
+    pub fn validate(&self, num_nodes: usize) -> Result<(), String> {
+        if self.sources.is_empty() {
+            return Err("the problem line declares no sources".to_string());
+        }
+        match self.sources.iter().find(|&&source| source >= num_nodes) {
+            Some(source) => Err(format!(
+                "source {} is outside of 1..={}",
+                source + 1,
+                num_nodes
+            )),
+            None => Ok(()),
+        }
+    }
+}
 
-    let mut sources = Vec::with_capacity(num_sources);
+impl ProblemSpecCO {
+    pub fn validate(&self, num_nodes: usize) -> Result<(), String> {
+        if self.num_nodes != num_nodes {
+            return Err(format!(
+                "coordinates are given for {} vertices, but the graph has {}",
+                self.num_nodes, num_nodes
+            ));
+        }
+        Ok(())
+    }
+}
 
-    loop {
-        if let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-            remaining_input = input;
-            continue;
-        } else {
-            match parse_source(remaining_input) {
-                Ok((input, source)) => {
-                    remaining_input = input;
-                    sources.push(source - 1);
-                }
-                Err(_) => {
-                    break;
-                }
-            }
+impl ProblemSpecP2P {
+    pub fn validate(&self, num_nodes: usize) -> Result<(), String> {
+        match self
+            .pairs
+            .iter()
+            .find(|&&(source, target)| source >= num_nodes || target >= num_nodes)
+        {
+            Some((source, target)) => Err(format!(
+                "query {} {} is outside of 1..={}",
+                source + 1,
+                target + 1,
+                num_nodes
+            )),
+            None => Ok(()),
         }
     }
+}
 
-    Ok((
-        remaining_input,
-        ProblemSpecSS {
-            num_sources,
-            sources,
-        },
-    ))
+// The line layout shared by all DIMACS files: comments starting with `c`, one problem
+// line starting with `p` that declares how many items follow, and the items themselves,
+// all starting with the same letter.
+struct Format {
+    problem: &'static str,
+    letter: &'static str,
+    item: &'static str,
+    items: &'static str,
 }
 
-pub fn parse_p2p(input: &str) -> IResult<&str, ProblemSpecP2P> {
-    let mut remaining_input = input;
-    // remove all comments from the be of the file
-    while let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-        remaining_input = input;
+const SS: Format = Format {
+    problem: "p aux sp ss SOURCES",
+    letter: "s",
+    item: "s SOURCE",
+    items: "sources",
+};
+
+const P2P: Format = Format {
+    problem: "p aux sp p2p PAIRS",
+    letter: "q",
+    item: "q SOURCE TARGET",
+    items: "pairs",
+};
+
+const CO: Format = Format {
+    problem: "p aux sp co NODES",
+    letter: "v",
+    item: "v ID X Y",
+    items: "coordinates",
+};
+
+fn number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse::<usize>)(input)
+}
+
+fn vertex(id: usize) -> Result<usize, ParseErrorKind> {
+    id.checked_sub(1).ok_or(ParseErrorKind::ZeroVertex)
+}
+
+fn vertex_in(id: usize, nodes: usize) -> Result<usize, ParseErrorKind> {
+    if id == 0 || id > nodes {
+        return Err(ParseErrorKind::VertexOutOfRange { vertex: id, nodes });
     }
+    Ok(id - 1)
+}
 
-    let (mut remaining_input, num_pairs) = parse_problem_p2p(remaining_input)?;
+// Parses a whole line with `parser`, which must consume all of it.
+fn parse_line<'a, O>(
+    line: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+    form: &'static str,
+) -> Result<O, ParseErrorKind> {
+    match all_consuming(parser)(line) {
+        Ok((_, output)) => Ok(output),
+        Err(_) => Err(ParseErrorKind::Malformed(form)),
+    }
+}
 
-    let mut pairs = Vec::with_capacity(num_pairs);
+// Goes through the lines of a file in `format`. The problem line is parsed by `problem`
+// into a header and the number of items it declares; every item line is passed to
+// `item` together with the header.
+fn parse_lines<'a, H: Copy>(
+    input: &'a str,
+    format: &Format,
+    mut problem: impl FnMut(&'a str) -> IResult<&'a str, (H, usize)>,
+    mut item: impl FnMut(H, &'a str) -> Result<(), ParseErrorKind>,
+) -> Result<H, ParseError> {
+    let mut header = None;
+    let mut found = 0;
+    let mut line_number = 0;
 
-    loop {
-        if let Ok((input, Some(_))) = opt(parse_comment)(remaining_input) {
-            remaining_input = input;
-            continue;
-        } else {
-            match parse_pair(remaining_input) {
-                Ok((input, pair)) => {
-                    remaining_input = input;
-                    pairs.push(pair);
-                }
-                Err(_) => {
-                    break;
+    for line in input.lines() {
+        line_number += 1;
+        let error = |kind| ParseError {
+            line: line_number,
+            kind,
+        };
+
+        let line = line.trim();
+        match (line.split_whitespace().next(), header) {
+            (None, _) => {}
+            (Some(first), _) if first.starts_with('c') => {}
+            (Some("p"), None) => {
+                let parsed = parse_line(line, &mut problem, format.problem).map_err(error)?;
+                header = Some((parsed, line_number));
+            }
+            (Some("p"), Some(_)) => return Err(error(ParseErrorKind::SecondProblem)),
+            (Some(first), None) if first == format.letter => {
+                return Err(error(ParseErrorKind::BeforeProblem(first.to_string())))
+            }
+            (Some(first), Some(((header, declared), _))) if first == format.letter => {
+                found += 1;
+                if found > declared {
+                    return Err(error(ParseErrorKind::TooMany {
+                        items: format.items,
+                        declared,
+                    }));
                 }
+                item(header, line).map_err(error)?;
             }
+            (Some(first), _) => return Err(error(ParseErrorKind::UnknownLine(first.to_string()))),
         }
     }
 
-    Ok((remaining_input, ProblemSpecP2P { num_pairs, pairs }))
+    match header {
+        None => Err(ParseError {
+            line: line_number + 1,
+            kind: ParseErrorKind::MissingProblem,
+        }),
+        Some(((_, declared), line)) if found < declared => Err(ParseError {
+            line,
+            kind: ParseErrorKind::TooFew {
+                items: format.items,
+                declared,
+                found,
+            },
+        }),
+        Some(((header, _), _)) => Ok(header),
+    }
+}
+
+pub fn parse_ss(input: &str) -> Result<ProblemSpecSS, ParseError> {
+    let mut sources = Vec::new();
+    let num_sources = parse_lines(
+        input,
+        &SS,
+        |line| {
+            let (line, (_, num_sources)) = tuple((tag("p aux sp ss "), number))(line)?;
+            Ok((line, (num_sources, num_sources)))
+        },
+        |_, line| {
+            let (_, _, source) = parse_line(line, tuple((tag("s"), space1, number)), SS.item)?;
+            sources.push(vertex(source)?);
+            Ok(())
+        },
+    )?;
+
+    Ok(ProblemSpecSS {
+        num_sources,
+        sources,
+    })
 }
 
-fn parse_pair(input: &str) -> IResult<&str, (usize, usize)> {
-    let (input, (_, _, source, _, target, _)) = tuple((
-        tag("q"),
-        multispace1,
-        map_res(digit1, str::parse::<usize>),
-        multispace1,
-        map_res(digit1, str::parse::<usize>),
-        line_ending,
-    ))(input)?;
-    Ok((
+pub fn parse_co(input: &str) -> Result<ProblemSpecCO, ParseError> {
+    let mut coordinates = Vec::new();
+    let num_nodes = parse_lines(
         input,
-        (
-            source - 1,
-            target - 1,
-        ),
-    ))
-}
-
-fn parse_problem_p2p(input: &str) -> IResult<&str, usize> {
-    let (input, (_, num_sources, _)) = tuple((
-        tag("p aux sp p2p "),
-        map_res(digit1, str::parse::<usize>),
-        line_ending,
-    ))(input)?;
-    Ok((input, num_sources))
-}
-
-pub fn parse_dimacs_gr_to_petgraph(input: &str) -> Result<Graph<(), u64, Undirected>, String> {
-    match parse_dimacs_gr(input) {
-        Ok((_, (num_nodes, num_edges, edges))) => {
-            let mut graph = Graph::<(), u64, Undirected>::with_capacity(num_nodes, num_edges);
-            let mut nodes = HashMap::with_capacity(num_nodes);
-            for edge in edges {
-                let source_node = *nodes
-                    .entry(edge.source)
-                    .or_insert_with(|| graph.add_node(()));
-                let target_node = *nodes
-                    .entry(edge.target)
-                    .or_insert_with(|| graph.add_node(()));
-                graph.add_edge(source_node, target_node, edge.weight);
+        &CO,
+        |line| {
+            let (line, (_, num_nodes)) = tuple((tag("p aux sp co "), number))(line)?;
+            Ok((line, (num_nodes, num_nodes)))
+        },
+        |num_nodes, line| {
+            let (_, _, id, _, x, _, y) = parse_line(
+                line,
+                tuple((tag("v"), space1, number, space1, signed, space1, signed)),
+                CO.item,
+            )?;
+            coordinates.resize(num_nodes, None);
+            let slot = &mut coordinates[vertex_in(id, num_nodes)?];
+            if slot.is_some() {
+                return Err(ParseErrorKind::DuplicateVertex(id));
             }
-            Ok(graph)
-        }
+            *slot = Some((x, y));
+            Ok(())
+        },
+    )?;
 
-        Err(e) => Err(format!("Parsing error: {:?}", e)),
-    }
+    // There is one line per vertex and no vertex has two, so every vertex has one.
+    let coordinates = coordinates.into_iter().map(Option::unwrap).collect();
+
+    Ok(ProblemSpecCO {
+        num_nodes,
+        coordinates,
+    })
+}
+
+pub fn parse_p2p(input: &str) -> Result<ProblemSpecP2P, ParseError> {
+    let mut pairs = Vec::new();
+    let num_pairs = parse_lines(
+        input,
+        &P2P,
+        |line| {
+            let (line, (_, num_pairs)) = tuple((tag("p aux sp p2p "), number))(line)?;
+            Ok((line, (num_pairs, num_pairs)))
+        },
+        |_, line| {
+            let (_, _, source, _, target) = parse_line(
+                line,
+                tuple((tag("q"), space1, number, space1, number)),
+                P2P.item,
+            )?;
+            pairs.push((vertex(source)?, vertex(target)?));
+            Ok(())
+        },
+    )?;
+
+    Ok(ProblemSpecP2P { num_pairs, pairs })
+}
+
+/// Reads the file at `path` and parses it with one of the `parse_*` functions, prefixing
+/// errors with the path.
+pub fn parse_file<T>(
+    path: &Path,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<T, String> {
+    let contents = read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parses the text of a `.gr` file with [`read_gr`], the same reader used for files.
+pub fn parse_dimacs_gr_to_petgraph<Ty: EdgeType>(
+    input: &str,
+) -> Result<Graph<(), u64, Ty>, ParseError> {
+    read_gr(Cursor::new(input))
+}
+
+/// Like [`parse_dimacs_gr_to_petgraph`], but keeps negative arc costs.
+pub fn parse_dimacs_gr_to_signed_petgraph<Ty: EdgeType>(
+    input: &str,
+) -> Result<Graph<(), i64, Ty>, ParseError> {
+    read_signed_gr(Cursor::new(input))
 }
@@ -0,0 +1,151 @@
+use std::marker::PhantomData;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::{
+    delta_stepping_all, dial_all, dial_single, dijkstra_all, dijkstra_all_with, dijkstra_single,
+    dijkstra_single_with, ShortestPathTree,
+};
+use crate::heap::{MonotonePriorityQueue, RadixHeap};
+
+/// A shortest path algorithm for non-negative arc costs that answers both one-to-all and
+/// point-to-point queries.
+pub trait ShortestPathSolver<Ty: EdgeType>: Sync {
+    /// Distances from `start` to every vertex.
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree;
+
+    /// Distance from `start` to `end`; distances of other vertices may be left tentative.
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> ShortestPathTree;
+}
+
+/// Dijkstra's algorithm with a binary heap.
+pub struct Dijkstra;
+
+impl<Ty: EdgeType> ShortestPathSolver<Ty> for Dijkstra {
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree {
+        dijkstra_all(graph, start)
+    }
+
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> ShortestPathTree {
+        dijkstra_single(graph, start, end)
+    }
+}
+
+/// Dijkstra's algorithm with the priority queue `Q`.
+pub struct DijkstraWith<Q>(PhantomData<fn() -> Q>);
+
+impl<Q> DijkstraWith<Q> {
+    pub fn new() -> DijkstraWith<Q> {
+        DijkstraWith(PhantomData)
+    }
+}
+
+impl<Q> Default for DijkstraWith<Q> {
+    fn default() -> Self {
+        DijkstraWith::new()
+    }
+}
+
+impl<Q: MonotonePriorityQueue, Ty: EdgeType> ShortestPathSolver<Ty> for DijkstraWith<Q> {
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree {
+        dijkstra_all_with::<Q, Ty>(graph, start)
+    }
+
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> ShortestPathTree {
+        dijkstra_single_with::<Q, Ty>(graph, start, end)
+    }
+}
+
+/// Dial's algorithm with `max_cost + 1` cyclic buckets.
+pub struct Dial {
+    pub max_cost: usize,
+}
+
+impl Dial {
+    /// Sizes the buckets for the largest arc cost of `graph`.
+    pub fn for_graph<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> Dial {
+        Dial {
+            max_cost: graph.edge_weights().copied().max().unwrap_or(0) as usize,
+        }
+    }
+}
+
+impl<Ty: EdgeType> ShortestPathSolver<Ty> for Dial {
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree {
+        dial_all(graph, start, self.max_cost)
+    }
+
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> ShortestPathTree {
+        dial_single(graph, start, end, self.max_cost)
+    }
+}
+
+/// Dijkstra's algorithm with a radix heap.
+pub struct Radix;
+
+impl<Ty: EdgeType> ShortestPathSolver<Ty> for Radix {
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree {
+        dijkstra_all_with::<RadixHeap, Ty>(graph, start)
+    }
+
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> ShortestPathTree {
+        dijkstra_single_with::<RadixHeap, Ty>(graph, start, end)
+    }
+}
+
+/// Parallel delta-stepping with buckets of width `delta`. Point-to-point queries run the
+/// full search, since buckets settle many vertices at once.
+pub struct DeltaStepping {
+    pub delta: u64,
+}
+
+impl DeltaStepping {
+    /// Uses the mean arc cost of `graph` as the bucket width.
+    pub fn for_graph<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> DeltaStepping {
+        let total: u64 = graph.edge_weights().sum();
+        DeltaStepping {
+            delta: (total / graph.edge_count().max(1) as u64).max(1),
+        }
+    }
+}
+
+impl<Ty: EdgeType> ShortestPathSolver<Ty> for DeltaStepping {
+    fn solve_all(&self, graph: &Graph<(), u64, Ty>, start: NodeIndex) -> ShortestPathTree {
+        delta_stepping_all(graph, start, self.delta)
+    }
+
+    fn solve_single(
+        &self,
+        graph: &Graph<(), u64, Ty>,
+        start: NodeIndex,
+        _end: NodeIndex,
+    ) -> ShortestPathTree {
+        delta_stepping_all(graph, start, self.delta)
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::{EdgeType, Graph};
+
+use crate::algorithms::ShortestPathTree;
+
+fn show(distance: u64) -> String {
+    match distance {
+        u64::MAX => "infinity".to_string(),
+        _ => distance.to_string(),
+    }
+}
+
+/// A vertex whose distance differs between two distance vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub vertex: NodeIndex,
+    pub left: u64,
+    pub right: u64,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "vertex {}: {} vs {}",
+            self.vertex.index() + 1,
+            show(self.left),
+            show(self.right)
+        )
+    }
+}
+
+/// The first vertex at which two distance vectors differ.
+pub fn first_mismatch(left: &[u64], right: &[u64]) -> Option<Mismatch> {
+    left.iter()
+        .zip(right)
+        .position(|(a, b)| a != b)
+        .map(|i| Mismatch {
+            vertex: NodeIndex::new(i),
+            left: left[i],
+            right: right[i],
+        })
+}
+
+/// Checks that `tree` is a shortest path tree from `start`: the source has distance 0 and
+/// no tree arc, no arc `(u, v)` of cost `w` has `d[v] > d[u] + w`, and every other reached
+/// vertex has a tight tree arc `(u, v)` with `d[v] = d[u] + w` on a path back to the source.
+/// Vertices are numbered from 1 in the returned message.
+pub fn check_optimality<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    start: NodeIndex,
+    tree: &ShortestPathTree,
+) -> Result<(), String> {
+    let distances = &tree.distances;
+    if distances[start.index()] != 0 {
+        return Err(format!(
+            "the source {} has distance {}",
+            start.index() + 1,
+            show(distances[start.index()])
+        ));
+    }
+    if tree.predecessors[start.index()].is_some() {
+        return Err(format!("the source {} has a tree arc", start.index() + 1));
+    }
+
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        let directions: &[(NodeIndex, NodeIndex)] = if graph.is_directed() {
+            &[(a, b)]
+        } else {
+            &[(a, b), (b, a)]
+        };
+        for &(u, v) in directions {
+            let (from, to) = (distances[u.index()], distances[v.index()]);
+            if from != u64::MAX && to > from.saturating_add(*edge.weight()) {
+                return Err(format!(
+                    "arc {} {} of cost {} shortens the distance of {} from {} to {}",
+                    u.index() + 1,
+                    v.index() + 1,
+                    edge.weight(),
+                    v.index() + 1,
+                    show(to),
+                    from.saturating_add(*edge.weight())
+                ));
+            }
+        }
+    }
+
+    for v in graph.node_indices() {
+        let distance = distances[v.index()];
+        match tree.predecessors[v.index()] {
+            None if v != start && distance != u64::MAX => {
+                return Err(format!(
+                    "vertex {} has distance {} but no tree arc",
+                    v.index() + 1,
+                    distance
+                ));
+            }
+            None => {}
+            Some((u, edge)) => {
+                let endpoints = graph.edge_endpoints(edge);
+                let connects =
+                    endpoints == Some((u, v)) || !graph.is_directed() && endpoints == Some((v, u));
+                if !connects {
+                    return Err(format!(
+                        "the tree arc of vertex {} does not lead from {} to it",
+                        v.index() + 1,
+                        u.index() + 1
+                    ));
+                }
+                let from = distances[u.index()];
+                let cost = graph[edge];
+                if from == u64::MAX || from.checked_add(cost) != Some(distance) {
+                    return Err(format!(
+                        "tree arc {} {} of cost {} is not tight: {} + {} != {}",
+                        u.index() + 1,
+                        v.index() + 1,
+                        cost,
+                        show(from),
+                        cost,
+                        show(distance)
+                    ));
+                }
+            }
+        }
+    }
+
+    // Tight tree arcs can still close a cycle of zero-cost arcs that never reaches the
+    // source; follow every chain of tree arcs, marking the vertices known to reach it.
+    const NEW: u8 = 0;
+    const ON_WALK: u8 = 1;
+    const DONE: u8 = 2;
+    let mut state = vec![NEW; distances.len()];
+    state[start.index()] = DONE;
+    for v in graph.node_indices() {
+        let mut walk = Vec::new();
+        let mut current = v;
+        while state[current.index()] == NEW {
+            state[current.index()] = ON_WALK;
+            walk.push(current);
+            match tree.predecessors[current.index()] {
+                Some((previous, _)) => current = previous,
+                None => break,
+            }
+        }
+        if state[current.index()] == ON_WALK && tree.predecessors[current.index()].is_some() {
+            return Err(format!(
+                "the tree arcs from vertex {} go around a cycle instead of to the source",
+                current.index() + 1
+            ));
+        }
+        for node in walk {
+            state[node.index()] = DONE;
+        }
+    }
+
+    Ok(())
+}
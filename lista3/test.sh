@@ -24,8 +24,14 @@ for folder in $input_folder/*; do
         filename=$(basename -- "$file")
         filename="${filename%.*}"
 
+        # convert the graph to the binary cache unless an up to date one exists
+        if [ ! "$folder/$filename.gr.bin" -nt "$folder/$filename.gr" ]; then
+            ./gr2bin -d "$folder/$filename.gr"
+        fi
+
         # run program on file and save the output to a file with timeout of 1 minute
         timeout 3m ./dial -d "$folder/$filename.gr" -ss "$file" -oss "$output_folder/dial_$filename.ss.res"
         timeout 3m ./dijkstra -d "$folder/$filename.gr" -ss "$file" -oss "$output_folder/dijkstra_$filename.ss.res"
+        timeout 3m ./radixheap -d "$folder/$filename.gr" -ss "$file" -oss "$output_folder/radixheap_$filename.ss.res"
     done
 done
\ No newline at end of file
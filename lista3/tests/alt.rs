@@ -0,0 +1,96 @@
+use std::io::{Cursor, ErrorKind};
+
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::dijkstra_all;
+use lista3::alt::{Landmarks, Selection};
+use lista3::astar::{astar_single, Potential};
+
+mod common;
+
+use common::{build, graphs};
+
+fn check_queries<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    landmarks: &Landmarks,
+) -> Result<(), TestCaseError> {
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s).distances;
+        for t in graph.node_indices() {
+            if expected[t.index()] != u64::MAX {
+                prop_assert!(landmarks.estimate(s, t) <= expected[t.index()]);
+            }
+            let tree = astar_single(graph, s, t, landmarks);
+            prop_assert_eq!(tree.distances[t.index()], expected[t.index()]);
+            let length = tree
+                .path(t)
+                .map(|route| route.edges.iter().map(|&e| graph[e]).sum::<u64>());
+            prop_assert_eq!(length.unwrap_or(u64::MAX), expected[t.index()]);
+        }
+    }
+    Ok(())
+}
+
+fn check<Ty: EdgeType>(graph: &Graph<(), u64, Ty>, count: usize) -> Result<(), TestCaseError> {
+    for selection in [Selection::Farthest, Selection::Avoid] {
+        let landmarks = Landmarks::select(graph, count, selection);
+        prop_assert_eq!(landmarks.landmarks.len(), count.min(graph.node_count()));
+        prop_assert!(landmarks.validate(graph).is_ok());
+        check_queries(graph, &landmarks)?;
+
+        let mut bytes = Vec::new();
+        landmarks.write_to(&mut bytes).unwrap();
+        let landmarks = Landmarks::read_from(&mut Cursor::new(&bytes)).unwrap();
+        prop_assert!(landmarks.validate(graph).is_ok());
+        check_queries(graph, &landmarks)?;
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn alt_queries_match_dijkstra_all_on_directed_graphs((n, edges) in graphs(), count in 0usize..6) {
+        check(&build::<Directed>(n, &edges), count)?;
+    }
+
+    #[test]
+    fn alt_queries_match_dijkstra_all_on_undirected_graphs((n, edges) in graphs(), count in 0usize..6) {
+        check(&build::<Undirected>(n, &edges), count)?;
+    }
+}
+
+#[test]
+fn landmarks_are_rejected_for_another_graph() {
+    let edges = [(0, 1, 5), (1, 2, 5)];
+    let landmarks = Landmarks::select(&build::<Directed>(3, &edges), 2, Selection::Avoid);
+
+    assert!(landmarks.validate(&build::<Undirected>(3, &edges)).is_err());
+    assert!(landmarks.validate(&build::<Directed>(4, &edges)).is_err());
+
+    let mut bytes = Vec::new();
+    landmarks.write_to(&mut bytes).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    assert!(Landmarks::read_from(&mut Cursor::new(&bytes)).is_err());
+}
+
+#[test]
+fn landmarks_files_with_a_wrong_length_are_rejected() {
+    let edges = [(0, 1, 5), (1, 2, 5)];
+    let landmarks = Landmarks::select(&build::<Directed>(3, &edges), 2, Selection::Avoid);
+    let mut bytes = Vec::new();
+    landmarks.write_to(&mut bytes).unwrap();
+
+    // The vertex count is at byte 9 and the landmark count at byte 25. Counts that do not
+    // fit the file are rejected before anything is allocated for them.
+    for (offset, count) in [(9, u64::MAX), (9, 1 << 40), (25, u64::MAX), (25, 1 << 20)] {
+        let mut corrupt = bytes.clone();
+        corrupt[offset..offset + 8].copy_from_slice(&count.to_le_bytes());
+        let error = Landmarks::read_from(&mut Cursor::new(&corrupt)).err();
+        let kind = error.map(|error| error.kind());
+        assert_eq!(kind, Some(ErrorKind::InvalidData), "count at {}", offset);
+    }
+
+    bytes.push(0);
+    assert!(Landmarks::read_from(&mut Cursor::new(&bytes)).is_err());
+}
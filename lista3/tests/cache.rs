@@ -0,0 +1,138 @@
+use std::fs::{read_to_string, write, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use petgraph::{Directed, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::cache::{cache_path, write_to, CacheFile, Fingerprint};
+use lista3::dimacs::{read_gr, read_gr_file, read_signed_gr_file};
+
+mod common;
+
+use common::{arc_list, gr};
+
+// A fresh path in the temporary directory, unique to this process and `name`.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lista3-{}-{}", std::process::id(), name))
+}
+
+fn write_cache(gr_path: &Path) {
+    let graph = read_gr::<Directed, _>(read_to_string(gr_path).unwrap().as_bytes()).unwrap();
+    let source = Fingerprint::of_file(gr_path).unwrap();
+    let mut file = File::create(cache_path(gr_path)).unwrap();
+    write_to(&graph, source, &mut file).unwrap();
+}
+
+fn load(path: &Path) -> io::Result<Graph<(), u64, Directed>> {
+    CacheFile::open(path)?.read_graph(|cost| cost)
+}
+
+proptest! {
+    #[test]
+    fn cached_graphs_match_the_gr_file(
+        (n, arcs) in (1usize..20).prop_flat_map(|n| {
+            (Just(n), prop::collection::vec((0..n, 0..n, 0..=u64::MAX / 2), 0..60))
+        })
+    ) {
+        let graph = read_gr::<Directed, _>(gr(n, &arcs).as_bytes()).unwrap();
+        let mut bytes = Vec::new();
+        write_to(&graph, Fingerprint { len: 0, modified: 0, checksum: 0 }, &mut bytes).unwrap();
+
+        let path = temp_path("roundtrip.bin");
+        write(&path, &bytes).unwrap();
+        let file = CacheFile::open(&path).unwrap();
+        let header = *file.header();
+        let graph = file.read_graph::<u64, Directed>(|cost| cost).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        prop_assert_eq!(header.node_count, n);
+        prop_assert_eq!(header.arc_count, arcs.len());
+        prop_assert_eq!(header.min_cost, arcs.iter().map(|arc| arc.2).min().unwrap_or(0));
+        prop_assert_eq!(header.max_cost, arcs.iter().map(|arc| arc.2).max().unwrap_or(0));
+        prop_assert_eq!(graph.node_count(), n);
+        prop_assert_eq!(arc_list(&graph), arcs);
+    }
+}
+
+#[test]
+fn fresh_caches_are_loaded_and_stale_ones_ignored() {
+    let gr_path = temp_path("fresh.gr");
+    write(&gr_path, gr(3, &[(0, 1, 5), (2, 0, 7), (0, 2, 1)])).unwrap();
+    write_cache(&gr_path);
+
+    let (graph, throughput) = read_gr_file::<Undirected>(&gr_path).unwrap();
+    assert!(throughput.cached);
+    assert_eq!(graph.edge_count(), 3);
+    let (graph, throughput) = read_signed_gr_file::<Directed>(&gr_path).unwrap();
+    assert!(throughput.cached);
+    assert_eq!(arc_list(&graph), [(0, 1, 5), (2, 0, 7), (0, 2, 1)]);
+
+    // Same length, different contents: the checksum tells them apart once the
+    // modification time differs.
+    write(&gr_path, gr(3, &[(0, 1, 5), (2, 0, 8), (0, 2, 1)])).unwrap();
+    File::options()
+        .write(true)
+        .open(&gr_path)
+        .unwrap()
+        .set_modified(UNIX_EPOCH + Duration::from_secs(1))
+        .unwrap();
+    let (graph, throughput) = read_gr_file::<Directed>(&gr_path).unwrap();
+    assert!(!throughput.cached);
+    assert_eq!(arc_list(&graph)[1], (2, 0, 8));
+
+    // A corrupt cache is skipped as well.
+    write(cache_path(&gr_path), b"LISTA3GR but not much else").unwrap();
+    assert!(load(&cache_path(&gr_path)).is_err());
+    let (_, throughput) = read_gr_file::<Directed>(&gr_path).unwrap();
+    assert!(!throughput.cached);
+
+    std::fs::remove_file(cache_path(&gr_path)).unwrap();
+    std::fs::remove_file(&gr_path).unwrap();
+}
+
+#[test]
+fn caches_with_a_wrong_cost_range_are_rejected() {
+    let gr_path = temp_path("costs.gr");
+    write(&gr_path, gr(2, &[(0, 1, 5), (1, 0, 7)])).unwrap();
+    write_cache(&gr_path);
+
+    // Bytes 24..40 hold the minimum and maximum cost.
+    let mut bytes = std::fs::read(cache_path(&gr_path)).unwrap();
+    bytes[32..40].copy_from_slice(&5u64.to_le_bytes());
+    write(cache_path(&gr_path), &bytes).unwrap();
+    assert!(load(&cache_path(&gr_path)).is_err());
+
+    bytes[24..40].copy_from_slice(&[0; 16]);
+    write(cache_path(&gr_path), &bytes).unwrap();
+    assert!(load(&cache_path(&gr_path)).is_err());
+    let (graph, throughput) = read_signed_gr_file::<Directed>(&gr_path).unwrap();
+    assert!(!throughput.cached);
+    assert_eq!(arc_list(&graph), [(0, 1, 5), (1, 0, 7)]);
+
+    std::fs::remove_file(cache_path(&gr_path)).unwrap();
+    std::fs::remove_file(&gr_path).unwrap();
+}
+
+#[test]
+fn caches_with_vertices_out_of_range_or_a_wrong_length_are_rejected() {
+    let gr_path = temp_path("vertices.gr");
+    write(&gr_path, gr(2, &[(0, 1, 5), (1, 0, 7)])).unwrap();
+    write_cache(&gr_path);
+    let bytes = std::fs::read(cache_path(&gr_path)).unwrap();
+    assert!(load(&cache_path(&gr_path)).is_ok());
+
+    // The first arc starts at byte 64 with its tail, then its head.
+    for offset in [64, 68] {
+        let mut corrupt = bytes.clone();
+        corrupt[offset] = 2;
+        write(cache_path(&gr_path), &corrupt).unwrap();
+        assert!(load(&cache_path(&gr_path)).is_err());
+    }
+    write(cache_path(&gr_path), &bytes[..bytes.len() - 1]).unwrap();
+    assert!(load(&cache_path(&gr_path)).is_err());
+
+    std::fs::remove_file(cache_path(&gr_path)).unwrap();
+    std::fs::remove_file(&gr_path).unwrap();
+}
@@ -0,0 +1,128 @@
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::dijkstra_all;
+use lista3::ch::ContractionHierarchy;
+
+mod common;
+
+use common::{build, graphs};
+
+fn check_queries<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    hierarchy: &ContractionHierarchy,
+) -> Result<(), TestCaseError> {
+    // One search for all queries, so that every query starts from the leftovers of another.
+    let mut search = hierarchy.search();
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s).distances;
+        for t in graph.node_indices() {
+            let path = search.query(s, t);
+            prop_assert_eq!(path.distance, expected[t.index()]);
+
+            prop_assert_eq!(path.route.is_some(), expected[t.index()] != u64::MAX);
+            if let Some(route) = path.route {
+                prop_assert_eq!(route.nodes[0], s);
+                prop_assert_eq!(route.nodes[route.nodes.len() - 1], t);
+                prop_assert_eq!(route.edges.len() + 1, route.nodes.len());
+                let mut length = 0;
+                for (i, &edge) in route.edges.iter().enumerate() {
+                    let (a, b) = graph.edge_endpoints(edge).unwrap();
+                    let (from, to) = (route.nodes[i], route.nodes[i + 1]);
+                    prop_assert!(
+                        (a, b) == (from, to) || !graph.is_directed() && (b, a) == (from, to)
+                    );
+                    length += graph[edge];
+                }
+                prop_assert_eq!(length, expected[t.index()]);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> Result<(), TestCaseError> {
+    let hierarchy = ContractionHierarchy::build(graph);
+    prop_assert!(hierarchy.validate(graph).is_ok());
+    check_queries(graph, &hierarchy)?;
+
+    let mut bytes = Vec::new();
+    hierarchy.write_to(&mut bytes).unwrap();
+    let hierarchy = ContractionHierarchy::read_from(&mut bytes.as_slice()).unwrap();
+    prop_assert!(hierarchy.validate(graph).is_ok());
+    check_queries(graph, &hierarchy)
+}
+
+proptest! {
+    #[test]
+    fn ch_queries_match_dijkstra_all_on_directed_graphs((n, edges) in graphs()) {
+        check(&build::<Directed>(n, &edges))?;
+    }
+
+    #[test]
+    fn ch_queries_match_dijkstra_all_on_undirected_graphs((n, edges) in graphs()) {
+        check(&build::<Undirected>(n, &edges))?;
+    }
+}
+
+#[test]
+fn hierarchy_is_rejected_for_another_graph() {
+    let edges = [(0, 1, 5), (1, 2, 5)];
+    let hierarchy = ContractionHierarchy::build(&build::<Directed>(3, &edges));
+
+    assert!(hierarchy.validate(&build::<Undirected>(3, &edges)).is_err());
+    assert!(hierarchy.validate(&build::<Directed>(4, &edges)).is_err());
+    assert!(hierarchy
+        .validate(&build::<Directed>(3, &edges[..1]))
+        .is_err());
+
+    let mut bytes = Vec::new();
+    hierarchy.write_to(&mut bytes).unwrap();
+    bytes[0] = b'X';
+    assert!(ContractionHierarchy::read_from(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn corrupt_hierarchy_files_are_rejected() {
+    let edges = [(0, 1, 5), (1, 2, 5)];
+    let hierarchy = ContractionHierarchy::build(&build::<Directed>(3, &edges));
+    let mut bytes = Vec::new();
+    hierarchy.write_to(&mut bytes).unwrap();
+    let read = |bytes: &[u8]| ContractionHierarchy::read_from(&mut &bytes[..]).err();
+
+    // Huge vertex and arc counts end in an error instead of a failed allocation. The vertex
+    // count is at byte 9, the upward arc count right after the three ranks.
+    for (offset, count) in [(9, u64::MAX), (9, 1 << 31), (37, u64::MAX), (37, 1 << 40)] {
+        let mut corrupt = bytes.clone();
+        corrupt[offset..offset + 8].copy_from_slice(&count.to_le_bytes());
+        assert!(read(&corrupt).is_some(), "{} at {}", count, offset);
+    }
+
+    // A hierarchy of 3 vertices ranked 1, 0 and 2, with one upward arc 0 -> 2 standing for
+    // `via` and no downward arcs.
+    let file = |via: u32| {
+        let mut file = bytes[..9].to_vec();
+        for value in [3u64, 2] {
+            file.extend(value.to_le_bytes());
+        }
+        for rank in [1u32, 0, 2] {
+            file.extend(rank.to_le_bytes());
+        }
+        file.extend(1u64.to_le_bytes());
+        for first in [0u64, 1, 1, 1] {
+            file.extend(first.to_le_bytes());
+        }
+        file.extend(2u32.to_le_bytes());
+        file.extend(10u64.to_le_bytes());
+        file.extend(via.to_le_bytes());
+        file.extend(0u64.to_le_bytes());
+        file.extend([0; 32]);
+        file
+    };
+    assert!(read(&file(1)).is_none());
+    let error = read(&file(2)).unwrap();
+    assert_eq!(error.to_string(), "arc refers to an input arc out of range");
+    // A shortcut through vertex 1 whose halves are not in the file.
+    let error = read(&file(1 | 1 << 31)).unwrap();
+    assert_eq!(error.to_string(), "shortcut without both of its halves");
+}
@@ -0,0 +1,77 @@
+// Graphs shared by the integration tests. Every test crate includes this module but uses
+// only some of the helpers, and the rest would be reported as dead code there.
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::{Directed, EdgeType, Graph};
+use proptest::prelude::*;
+
+/// Arcs of a graph on `n` vertices, as `(tail, head, cost)` with 0-based vertex ids.
+pub type Arcs<W> = (usize, Vec<(usize, usize, W)>);
+
+fn build_with<W: Copy, Ty: EdgeType>(n: usize, edges: &[(usize, usize, W)]) -> Graph<(), W, Ty> {
+    let mut graph = Graph::<(), W, Ty>::default();
+    for _ in 0..n {
+        graph.add_node(());
+    }
+    for &(u, v, w) in edges {
+        graph.add_edge(NodeIndex::new(u), NodeIndex::new(v), w);
+    }
+    graph
+}
+
+// Graph on `n` vertices with the arcs in the given order.
+pub fn build<Ty: EdgeType>(n: usize, edges: &[(usize, usize, u64)]) -> Graph<(), u64, Ty> {
+    build_with(n, edges)
+}
+
+// Like `build`, with negative costs allowed.
+pub fn build_signed<Ty: EdgeType>(n: usize, edges: &[(usize, usize, i64)]) -> Graph<(), i64, Ty> {
+    build_with(n, edges)
+}
+
+// The arcs of `graph` in insertion order, as `build` takes them.
+pub fn arc_list<W: Copy>(graph: &Graph<(), W, Directed>) -> Vec<(usize, usize, W)> {
+    graph
+        .edge_references()
+        .map(|edge| (edge.source().index(), edge.target().index(), *edge.weight()))
+        .collect()
+}
+
+// The `.gr` file of a graph on `n` vertices.
+pub fn gr(n: usize, arcs: &[(usize, usize, u64)]) -> String {
+    let mut text = format!("p sp {} {}\n", n, arcs.len());
+    for &(u, v, w) in arcs {
+        text.push_str(&format!("a {} {} {}\n", u + 1, v + 1, w));
+    }
+    text
+}
+
+// Graphs with a vertex count from `nodes`, an arc count from `arcs` and costs from `costs`.
+// Parallel arcs and loops are allowed.
+pub fn graphs_with<S>(
+    nodes: Range<usize>,
+    arcs: Range<usize>,
+    costs: S,
+) -> impl Strategy<Value = Arcs<S::Value>>
+where
+    S: Strategy + Clone,
+    S::Value: Copy + Debug,
+{
+    nodes.prop_flat_map(move |n| {
+        (
+            Just(n),
+            prop::collection::vec((0..n, 0..n, costs.clone()), arcs.clone()),
+        )
+    })
+}
+
+// Small graphs whose costs go up to 1, 10 or 1000, so that both ties and long arcs are common.
+pub fn graphs() -> impl Strategy<Value = Arcs<u64>> {
+    prop_oneof![Just(1u64), Just(10), Just(1000)]
+        .prop_flat_map(|max_w| graphs_with(1..25, 0..80, 0..=max_w))
+}
@@ -0,0 +1,52 @@
+f random-200.gr random-200.p2p
+g 200 900 0 1000
+d 182 19 1293
+d 151 140 366
+d 52 25 1588
+d 195 72 18446744073709551615
+d 192 182 18446744073709551615
+d 131 68 1269
+d 130 67 864
+d 124 80 954
+d 149 2 1465
+d 36 176 1169
+d 81 91 2113
+d 96 132 861
+d 157 96 1583
+d 181 57 819
+d 52 161 1535
+d 20 50 881
+d 117 86 1532
+d 121 108 2114
+d 127 146 1501
+d 166 26 171
+d 99 44 1698
+d 68 66 748
+d 100 76 778
+d 105 13 922
+d 191 87 18446744073709551615
+d 70 185 967
+d 74 69 259
+d 27 152 1676
+d 196 23 18446744073709551615
+d 164 91 2122
+d 183 164 2333
+d 108 11 975
+d 109 93 18446744073709551615
+d 200 89 18446744073709551615
+d 121 191 18446744073709551615
+d 164 39 915
+d 65 67 838
+d 177 190 1557
+d 106 29 2073
+d 181 54 769
+d 30 75 1349
+d 165 3 1044
+d 69 152 1177
+d 91 77 1490
+d 71 184 961
+d 58 87 943
+d 187 114 1428
+d 18 103 930
+d 150 69 1009
+d 27 86 1289
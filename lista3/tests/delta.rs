@@ -0,0 +1,68 @@
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::{delta_stepping_all, dijkstra_all};
+
+mod common;
+
+use common::{build, graphs};
+
+fn check<Ty: EdgeType>(graph: &Graph<(), u64, Ty>, delta: u64) -> Result<(), TestCaseError> {
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s);
+        let tree = delta_stepping_all(graph, s, delta);
+        prop_assert_eq!(&tree.distances, &expected.distances);
+        prop_assert_eq!(tree.settled, expected.settled);
+
+        for t in graph.node_indices() {
+            let length = tree
+                .path(t)
+                .map(|route| route.edges.iter().map(|&e| graph[e]).sum::<u64>());
+            let finite = (expected.distances[t.index()] != u64::MAX)
+                .then_some(expected.distances[t.index()]);
+            prop_assert_eq!(length, finite);
+        }
+    }
+    Ok(())
+}
+
+fn deltas() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(1u64), 2..20u64, 20..2000u64]
+}
+
+proptest! {
+    #[test]
+    fn delta_stepping_matches_dijkstra_all_on_directed_graphs(
+        (n, edges) in graphs(),
+        delta in deltas(),
+    ) {
+        check(&build::<Directed>(n, &edges), delta)?;
+    }
+
+    #[test]
+    fn delta_stepping_matches_dijkstra_all_on_undirected_graphs(
+        (n, edges) in graphs(),
+        delta in deltas(),
+    ) {
+        check(&build::<Undirected>(n, &edges), delta)?;
+    }
+}
+
+#[test]
+fn delta_stepping_handles_a_large_frontier() {
+    // A star whose leaves all land in the same bucket, so the phase is split between threads.
+    let n = 10_000;
+    let edges: Vec<_> = (1..n)
+        .map(|v| (0, v, (v % 7) as u64))
+        .chain((1..n - 1).map(|v| (v, v + 1, 1)))
+        .collect();
+    let graph = build::<Directed>(n, &edges);
+    for delta in [1, 8, 100] {
+        let tree = delta_stepping_all(&graph, NodeIndex::new(0), delta);
+        assert_eq!(
+            tree.distances,
+            dijkstra_all(&graph, NodeIndex::new(0)).distances
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use petgraph::{Directed, Undirected};
+use proptest::prelude::*;
+
+use lista3::dimacs::{read_gr, read_gr_file, read_signed_gr};
+use lista3::parsing::{parse_dimacs_gr_to_petgraph, parse_dimacs_gr_to_signed_petgraph};
+
+mod common;
+
+use common::{arc_list, graphs_with, Arcs};
+
+// Like `common::gr`, with comment lines between some of the arcs.
+fn gr(n: usize, arcs: &[(usize, usize, u64)]) -> String {
+    let mut text = format!("c random graph\np sp {} {}\n", n, arcs.len());
+    for (i, &(u, v, w)) in arcs.iter().enumerate() {
+        if i % 7 == 3 {
+            text.push_str("c a comment between arcs\n");
+        }
+        text.push_str(&format!("a {} {} {}\n", u + 1, v + 1, w));
+    }
+    text
+}
+
+// Costs up to half of `u64::MAX`, so that the parsers see every digit count.
+fn graphs() -> impl Strategy<Value = Arcs<u64>> {
+    graphs_with(1..25, 0..80, 0..=u64::MAX / 2)
+}
+
+proptest! {
+    #[test]
+    fn streaming_reader_keeps_the_arcs_in_file_order((n, arcs) in graphs()) {
+        let text = gr(n, &arcs);
+        let graph = read_gr::<Directed, _>(text.as_bytes()).unwrap();
+        prop_assert_eq!(graph.node_count(), n);
+        prop_assert_eq!(arc_list(&graph), arcs);
+    }
+}
+
+#[test]
+fn checked_in_graph_file_is_read_like_its_text() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/random-200.gr");
+    let expected =
+        parse_dimacs_gr_to_petgraph::<Undirected>(&read_to_string(&path).unwrap()).unwrap();
+    let (graph, throughput) = read_gr_file::<Undirected>(&path).unwrap();
+    assert_eq!(graph.node_count(), expected.node_count());
+    assert!(graph
+        .raw_edges()
+        .iter()
+        .zip(expected.raw_edges())
+        .all(|(a, b)| (a.source(), a.target(), a.weight) == (b.source(), b.target(), b.weight)));
+    assert_eq!(throughput.bytes, std::fs::metadata(&path).unwrap().len());
+}
+
+#[test]
+fn malformed_files_are_rejected_with_line_numbers() {
+    let cases = [
+        ("p sp 2 1\na 1 2 -5\n", "line 2: negative arc cost -5"),
+        (
+            "p sp 2 1\na 1 3 5\n",
+            "line 2: vertex 3 is outside of 1..=2",
+        ),
+        (
+            "c no problem line\na 1 2 5\n",
+            "line 2: 'a' line before the problem line",
+        ),
+        ("p sp 2 1\na 1 2\n", "line 2: expected 'a TAIL HEAD COST'"),
+        (
+            "p sp 2 1\na 1 2 5 6\n",
+            "line 2: expected 'a TAIL HEAD COST'",
+        ),
+        ("p sp 2\n", "line 1: expected 'p sp NODES ARCS'"),
+        ("p sp 2 1\nc fine\nx 1 2\n", "line 3: unknown line type 'x'"),
+        ("p sp 2 1\np sp 2 1\n", "line 2: second problem line"),
+        ("c empty\n", "line 2: missing problem line"),
+        (
+            "p sp 2 1\na 1 2 5\n\na 2 1 5\n",
+            "line 4: more arcs than the 1 declared on the problem line",
+        ),
+        (
+            "c header\np sp 2 3\na 1 2 5\r\na 2 1 5",
+            "line 2: the problem line declares 3 arcs, but the file has 2",
+        ),
+    ];
+    for (text, message) in cases {
+        let error = read_gr::<Directed, _>(text.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), message, "{:?}", text);
+        let parsed = parse_dimacs_gr_to_petgraph::<Directed>(text).unwrap_err();
+        assert_eq!(error, parsed, "{:?}", text);
+    }
+
+    let graph = read_signed_gr::<Directed, _>("p sp 2 1\na 1 2 -5\n".as_bytes()).unwrap();
+    assert_eq!(graph.raw_edges()[0].weight, -5);
+}
+
+#[test]
+fn problem_lines_past_32_bit_ids_are_rejected_before_allocating() {
+    let cases = [
+        (
+            "p sp 4294967296 1
+",
+            "line 1: 4294967296 vertices do not fit in 32-bit ids",
+        ),
+        (
+            "p sp 2 4294967296
+",
+            "line 1: more arcs than fit in 32-bit ids",
+        ),
+    ];
+    for (text, message) in cases {
+        let error = read_gr::<Directed, _>(text.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), message);
+        let error = read_signed_gr::<Undirected, _>(text.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), message);
+    }
+}
+
+#[test]
+fn crlf_and_missing_final_newline_are_accepted() {
+    let text = "c header\r\np sp 2 2\r\na 1 2 5\r\n\r\na 2 1 7";
+    let graph = read_gr::<Directed, _>(text.as_bytes()).unwrap();
+    let expected = parse_dimacs_gr_to_petgraph::<Directed>(text).unwrap();
+    assert_eq!(graph.edge_count(), 2);
+    assert_eq!(expected.edge_count(), 2);
+}
+
+#[test]
+fn runs_of_spaces_and_tabs_separate_fields() {
+    let text = "p  sp\t2 2\na 1\t\t2  5\n a 2 1 7 \n";
+    let arcs = vec![(0, 1, 5), (1, 0, 7)];
+    let graph = parse_dimacs_gr_to_petgraph::<Directed>(text).unwrap();
+    assert_eq!(arc_list(&graph), arcs);
+    let signed = parse_dimacs_gr_to_signed_petgraph::<Directed>(text).unwrap();
+    assert_eq!(signed.edge_count(), 2);
+}
@@ -0,0 +1,62 @@
+use std::fs::{read_to_string, remove_file, write};
+use std::path::PathBuf;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, Graph};
+
+use lista3::driver::{run_p2p, run_ss, Answer, CommonArgs};
+
+// A fresh path in the temporary directory, unique to this process and `name`.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lista3-{}-{}", std::process::id(), name))
+}
+
+fn args(queries: Option<PathBuf>, results: PathBuf) -> CommonArgs {
+    CommonArgs {
+        gr_path: "empty.gr".into(),
+        ss_path: queries.clone(),
+        oss_path: Some(results.clone()),
+        p2p_path: queries,
+        op2p_path: Some(results),
+        paths_path: None,
+        threads: None,
+        undirected: false,
+    }
+}
+
+#[test]
+fn graphs_without_arcs_and_empty_source_files_are_handled() {
+    let mut graph = Graph::<(), i64, Directed>::default();
+    graph.add_node(());
+    graph.add_node(());
+
+    let p2p_path = temp_path("no-arcs.p2p");
+    let results = temp_path("no-arcs.p2p.res");
+    write(&p2p_path, "p aux sp p2p 1\nq 1 2\n").unwrap();
+    let answer = |_, _, _| Answer {
+        distance: i64::MAX,
+        settled: None,
+        route: None,
+    };
+    run_p2p(
+        &graph,
+        &args(Some(p2p_path.clone()), results.clone()),
+        &p2p_path,
+        answer,
+        Vec::new,
+    )
+    .unwrap();
+    let expected = format!("f empty.gr {}\ng 2 0 0 0\nd 1 2 inf\n", p2p_path.display());
+    assert_eq!(read_to_string(&results).unwrap(), expected);
+
+    // There is no mean time of no runs, so a file without sources is an error.
+    let ss_path = temp_path("no-sources.ss");
+    write(&ss_path, "p aux sp ss 0\n").unwrap();
+    let args = args(Some(ss_path.clone()), results.clone());
+    let error = run_ss(&graph, &args, &ss_path, |_: NodeIndex| (), |_| 0).unwrap_err();
+    assert_eq!(error, "the problem line declares no sources");
+
+    for path in [p2p_path, results, ss_path] {
+        let _ = remove_file(path);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::{dijkstra_all, dijkstra_all_with, dijkstra_single_with};
+use lista3::heap::{
+    DaryHeap, FibonacciHeap, LazyBinaryHeap, MonotonePriorityQueue, PairingHeap, RadixHeap,
+};
+
+mod common;
+
+use common::{build, graphs};
+
+const NODES: usize = 20;
+
+#[derive(Clone, Debug)]
+enum Operation {
+    // Key relative to the last popped one, so the keys stay monotone.
+    Push(usize, u64),
+    Pop,
+}
+
+fn operations() -> impl Strategy<Value = Vec<Operation>> {
+    prop::collection::vec(
+        prop_oneof![
+            2 => (0..NODES, 0u64..30).prop_map(|(node, key)| Operation::Push(node, key)),
+            1 => Just(Operation::Pop),
+        ],
+        0..300,
+    )
+}
+
+// Replays the operations against a map of the queued keys, which every pop must agree with.
+fn check_queue<Q: MonotonePriorityQueue>(operations: &[Operation]) -> Result<(), TestCaseError> {
+    let mut queue = Q::new(NODES);
+    let mut queued = HashMap::new();
+    let mut last = 0;
+
+    for operation in operations
+        .iter()
+        .chain([Operation::Pop].iter().cycle().take(NODES + 1))
+    {
+        match *operation {
+            Operation::Push(node, key) => {
+                let key = last + key;
+                queue.push(node, key);
+                let entry = queued.entry(node).or_insert(key);
+                *entry = (*entry).min(key);
+            }
+            Operation::Pop => {
+                let popped = queue.pop();
+                let min = queued.values().copied().min();
+                prop_assert_eq!(popped.map(|(key, _)| key), min);
+                if let Some((key, node)) = popped {
+                    prop_assert_eq!(queued.remove(&node), Some(key));
+                    last = key;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_dijkstra_with<Q: MonotonePriorityQueue, Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+) -> Result<(), TestCaseError> {
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s);
+        let tree = dijkstra_all_with::<Q, Ty>(graph, s);
+        prop_assert_eq!(&tree.distances, &expected.distances);
+        prop_assert_eq!(tree.settled, expected.settled);
+        for t in graph.node_indices() {
+            let tree = dijkstra_single_with::<Q, Ty>(graph, s, t);
+            prop_assert_eq!(tree.distances[t.index()], expected.distances[t.index()]);
+            let length = tree
+                .path(t)
+                .map(|route| route.edges.iter().map(|&e| graph[e]).sum::<u64>());
+            prop_assert_eq!(length.unwrap_or(u64::MAX), expected.distances[t.index()]);
+        }
+    }
+    Ok(())
+}
+
+fn check_dijkstra<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> Result<(), TestCaseError> {
+    check_dijkstra_with::<LazyBinaryHeap, Ty>(graph)?;
+    check_dijkstra_with::<DaryHeap<2>, Ty>(graph)?;
+    check_dijkstra_with::<DaryHeap<4>, Ty>(graph)?;
+    check_dijkstra_with::<PairingHeap, Ty>(graph)?;
+    check_dijkstra_with::<RadixHeap, Ty>(graph)?;
+    check_dijkstra_with::<FibonacciHeap, Ty>(graph)
+}
+
+proptest! {
+    #[test]
+    fn indexed_heaps_pop_the_smallest_key(operations in operations()) {
+        check_queue::<DaryHeap<2>>(&operations)?;
+        check_queue::<DaryHeap<3>>(&operations)?;
+        check_queue::<DaryHeap<8>>(&operations)?;
+        check_queue::<PairingHeap>(&operations)?;
+        check_queue::<FibonacciHeap>(&operations)?;
+    }
+
+    #[test]
+    fn dijkstra_with_any_heap_matches_dijkstra_all_on_directed_graphs((n, edges) in graphs()) {
+        check_dijkstra(&build::<Directed>(n, &edges))?;
+    }
+
+    #[test]
+    fn dijkstra_with_any_heap_matches_dijkstra_all_on_undirected_graphs((n, edges) in graphs()) {
+        check_dijkstra(&build::<Undirected>(n, &edges))?;
+    }
+}
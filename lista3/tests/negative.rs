@@ -0,0 +1,184 @@
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::ShortestPathTree;
+use lista3::negative::{bellman_ford, goldberg_radzik, spfa, NegativeCycle, SignedError};
+use lista3::parsing::{parse_dimacs_gr_to_petgraph, parse_dimacs_gr_to_signed_petgraph};
+
+mod common;
+
+use common::{build_signed, graphs_with, Arcs};
+
+// Floyd–Warshall over the arcs, in both directions for undirected graphs.
+fn all_pairs<Ty: EdgeType>(graph: &Graph<(), i64, Ty>) -> Vec<Vec<Option<i64>>> {
+    let n = graph.node_count();
+    let mut distance = vec![vec![None; n]; n];
+    for (v, row) in distance.iter_mut().enumerate() {
+        row[v] = Some(0);
+    }
+    for edge in graph.raw_edges() {
+        let (u, v) = (edge.source().index(), edge.target().index());
+        let mut arcs = vec![(u, v)];
+        if !graph.is_directed() {
+            arcs.push((v, u));
+        }
+        for (a, b) in arcs {
+            if distance[a][b].is_none_or(|d| edge.weight < d) {
+                distance[a][b] = Some(edge.weight);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                if let (Some(a), Some(b)) = (distance[i][k], distance[k][j]) {
+                    if distance[i][j].is_none_or(|d| a + b < d) {
+                        distance[i][j] = Some(a + b);
+                    }
+                }
+            }
+        }
+    }
+    distance
+}
+
+fn check_cycle<Ty: EdgeType>(
+    graph: &Graph<(), i64, Ty>,
+    cycle: &NegativeCycle,
+) -> Result<i64, TestCaseError> {
+    prop_assert_eq!(cycle.nodes.len(), cycle.edges.len());
+    prop_assert!(!cycle.nodes.is_empty());
+    let mut cost = 0;
+    for (i, &edge) in cycle.edges.iter().enumerate() {
+        let (a, b) = graph.edge_endpoints(edge).unwrap();
+        let from = cycle.nodes[i];
+        let to = cycle.nodes[(i + 1) % cycle.nodes.len()];
+        prop_assert!((a, b) == (from, to) || !graph.is_directed() && (b, a) == (from, to));
+        cost += graph[edge];
+    }
+    prop_assert!(cost < 0);
+    Ok(cost)
+}
+
+fn check<Ty: EdgeType>(graph: &Graph<(), i64, Ty>) -> Result<(), TestCaseError> {
+    let expected = all_pairs(graph);
+    for s in graph.node_indices() {
+        // A negative cycle is reachable from `s` iff some vertex it reaches lies on one.
+        let negative_cycle = graph.node_indices().any(|v| {
+            expected[s.index()][v.index()].is_some()
+                && expected[v.index()][v.index()].is_some_and(|d| d < 0)
+        });
+
+        for result in [
+            bellman_ford(graph, s),
+            spfa(graph, s),
+            goldberg_radzik(graph, s),
+        ] {
+            match result {
+                Ok(tree) => {
+                    prop_assert!(!negative_cycle);
+                    for t in graph.node_indices() {
+                        let distance = tree.distances[t.index()];
+                        prop_assert_eq!(
+                            (distance != i64::MAX).then_some(distance),
+                            expected[s.index()][t.index()]
+                        );
+                        let length = tree
+                            .path(t)
+                            .map(|route| route.edges.iter().map(|&e| graph[e]).sum::<i64>());
+                        prop_assert_eq!(length, expected[s.index()][t.index()]);
+                    }
+                }
+                Err(SignedError::Overflow { node }) => {
+                    return Err(TestCaseError::fail(format!("overflow at {:?}", node)));
+                }
+                Err(SignedError::NegativeCycle(cycle)) => {
+                    prop_assert!(negative_cycle);
+                    check_cycle(graph, &cycle)?;
+                    prop_assert!(expected[s.index()][cycle.nodes[0].index()].is_some());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn graphs() -> impl Strategy<Value = Arcs<i64>> {
+    prop_oneof![Just(0i64), Just(2), Just(10)]
+        .prop_flat_map(|min_w| graphs_with(1..15, 0..40, -min_w..=20))
+}
+
+proptest! {
+    #[test]
+    fn signed_solvers_agree_with_floyd_warshall_on_directed_graphs((n, edges) in graphs()) {
+        check(&build_signed::<Directed>(n, &edges))?;
+    }
+
+    #[test]
+    fn signed_solvers_agree_with_floyd_warshall_on_undirected_graphs((n, edges) in graphs()) {
+        check(&build_signed::<Undirected>(n, &edges))?;
+    }
+}
+
+#[test]
+fn negative_costs_are_only_accepted_by_the_signed_parser() {
+    let input = "p sp 3 3\na 1 2 6\na 2 3 -2\na 3 1 -3\n";
+    assert!(parse_dimacs_gr_to_petgraph::<Directed>(input).is_err());
+
+    let graph = parse_dimacs_gr_to_signed_petgraph::<Directed>(input).unwrap();
+    assert_eq!(
+        graph.edge_weights().copied().collect::<Vec<_>>(),
+        [6, -2, -3]
+    );
+    let tree = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+    assert_eq!(tree.distances, [0, 6, 4]);
+}
+
+#[test]
+fn checksums_sum_finite_distances_modulo_2_to_the_62() {
+    let graph =
+        build_signed::<Directed>(4, &[(0, 1, i64::MAX / 2), (1, 2, i64::MAX / 4), (0, 3, -3)]);
+    let tree = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+    let sum = tree
+        .distances
+        .iter()
+        .map(|&distance| distance as i128)
+        .sum::<i128>();
+    assert_eq!(tree.checksum() as i128, sum.rem_euclid(1 << 62));
+
+    let unsigned = ShortestPathTree {
+        distances: vec![0, u64::MAX, u64::MAX - 1, 1 << 62, 5],
+        predecessors: vec![None; 5],
+        settled: 5,
+    };
+    let sum = (u64::MAX - 1) as u128 + (1 << 62) + 5;
+    assert_eq!(unsigned.checksum() as u128, sum % (1 << 62));
+
+    let unreachable = bellman_ford(
+        &build_signed::<Directed>(2, &[(1, 0, -1)]),
+        NodeIndex::new(0),
+    );
+    assert_eq!(unreachable.unwrap().checksum(), 0);
+}
+
+#[test]
+fn overflowing_distances_are_reported() {
+    // The distance of vertex 3 is just past either end of the range, where i64::MAX is
+    // taken as well, since it marks unreachable vertices.
+    for costs in [[i64::MAX - 1, 1], [i64::MIN, -1]] {
+        let graph = build_signed::<Directed>(3, &[(0, 1, costs[0]), (1, 2, costs[1])]);
+        let start = NodeIndex::new(0);
+        for result in [
+            bellman_ford(&graph, start),
+            spfa(&graph, start),
+            goldberg_radzik(&graph, start),
+        ] {
+            assert!(matches!(result, Err(SignedError::Overflow { node }) if node.index() == 2));
+        }
+    }
+
+    let graph = build_signed::<Directed>(3, &[(0, 1, i64::MIN + 1), (1, 2, -1)]);
+    let tree = goldberg_radzik(&graph, NodeIndex::new(0)).unwrap();
+    assert_eq!(tree.distances, [0, i64::MIN + 1, i64::MIN]);
+}
@@ -0,0 +1,187 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::{
+    bidirectional_dijkstra_single, dial_single, dijkstra_all, dijkstra_single,
+    dijkstra_single_with, ShortestPathTree,
+};
+use lista3::astar::{astar_single, Heuristic, Metric, Potential};
+use lista3::heap::RadixHeap;
+use lista3::parsing::{parse_co, parse_dimacs_gr_to_petgraph, parse_p2p};
+use lista3::solver::{DeltaStepping, Dial, Dijkstra, Radix, ShortestPathSolver};
+
+mod common;
+
+use common::{build, graphs};
+
+fn route_length<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    tree: &ShortestPathTree,
+    end: NodeIndex,
+) -> Option<u64> {
+    tree.path(end)
+        .map(|route| route.edges.iter().map(|&e| graph[e]).sum())
+}
+
+fn check_all_pairs<Ty: EdgeType>(graph: &Graph<(), u64, Ty>) -> Result<(), TestCaseError> {
+    let max_cost = graph.edge_weights().copied().max().unwrap_or(0) as usize;
+
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s).distances;
+        for t in graph.node_indices() {
+            let finite = (expected[t.index()] != u64::MAX).then_some(expected[t.index()]);
+            for tree in [
+                dijkstra_single(graph, s, t),
+                bidirectional_dijkstra_single(graph, s, t),
+                dial_single(graph, s, t, max_cost),
+                dijkstra_single_with::<RadixHeap, _>(graph, s, t),
+            ] {
+                prop_assert_eq!(tree.distances[t.index()], expected[t.index()]);
+                prop_assert_eq!(route_length(graph, &tree, t), finite);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_astar<Ty: EdgeType>(
+    graph: &Graph<(), u64, Ty>,
+    coordinates: &[(i64, i64)],
+) -> Result<(), TestCaseError> {
+    for metric in [Metric::Euclidean, Metric::GreatCircle] {
+        let heuristic = Heuristic::new(graph, coordinates, metric);
+        for s in graph.node_indices() {
+            let expected = dijkstra_all(graph, s).distances;
+            for t in graph.node_indices() {
+                let finite = (expected[t.index()] != u64::MAX).then_some(expected[t.index()]);
+                prop_assert!(heuristic.estimate(s, t) <= expected[t.index()]);
+                let tree = astar_single(graph, s, t, &heuristic);
+                prop_assert_eq!(tree.distances[t.index()], expected[t.index()]);
+                prop_assert_eq!(route_length(graph, &tree, t), finite);
+            }
+        }
+    }
+    Ok(())
+}
+
+type GraphWithCoordinates = (usize, Vec<(usize, usize, u64)>, Vec<(i64, i64)>);
+
+// Coordinates in millionths of a degree around the continental USA, with arc costs
+// unrelated to them, so the heuristic scale comes out small.
+fn graphs_with_coordinates() -> impl Strategy<Value = GraphWithCoordinates> {
+    (1usize..25).prop_flat_map(|n| {
+        (
+            Just(n),
+            prop::collection::vec((0..n, 0..n, 0u64..2_000_000), 0..80),
+            prop::collection::vec((-125_000_000i64..-67_000_000, 25_000_000i64..49_000_000), n),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn p2p_matches_dijkstra_all_on_directed_graphs((n, edges) in graphs()) {
+        check_all_pairs(&build::<Directed>(n, &edges))?;
+    }
+
+    #[test]
+    fn p2p_matches_dijkstra_all_on_undirected_graphs((n, edges) in graphs()) {
+        check_all_pairs(&build::<Undirected>(n, &edges))?;
+    }
+
+    #[test]
+    fn astar_matches_dijkstra_all((n, edges, coordinates) in graphs_with_coordinates()) {
+        check_astar(&build::<Directed>(n, &edges), &coordinates)?;
+        check_astar(&build::<Undirected>(n, &edges), &coordinates)?;
+    }
+}
+
+// Distances from `source` by relaxing every arc until nothing changes, independent of the
+// solvers under test.
+fn reference_distances(graph: &Graph<(), u64, Directed>, source: usize) -> Vec<Option<u64>> {
+    let mut distances = vec![None; graph.node_count()];
+    distances[source] = Some(0);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for edge in graph.raw_edges() {
+            if let Some(d) = distances[edge.source().index()] {
+                let next = Some(d + edge.weight);
+                let target = &mut distances[edge.target().index()];
+                if target.is_none() || next < *target {
+                    *target = next;
+                    changed = true;
+                }
+            }
+        }
+    }
+    distances
+}
+
+#[test]
+fn checked_in_p2p_results_match_a_reference_search() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let gr = read_to_string(data.join("random-200.gr")).unwrap();
+    let p2p = read_to_string(data.join("random-200.p2p")).unwrap();
+    let res = read_to_string(data.join("random-200.p2p.res")).unwrap();
+
+    let graph = parse_dimacs_gr_to_petgraph::<Directed>(&gr).unwrap();
+    let p2p = parse_p2p(&p2p).unwrap();
+    let max_cost = *graph.edge_weights().max().unwrap() as usize;
+    let solvers: [Box<dyn ShortestPathSolver<Directed>>; 4] = [
+        Box::new(Dijkstra),
+        Box::new(Dial::for_graph(&graph)),
+        Box::new(Radix),
+        Box::new(DeltaStepping::for_graph(&graph)),
+    ];
+
+    // Unreachable targets have distance `u64::MAX` in the trees and in the results file.
+    let expected: Vec<u64> = p2p
+        .pairs
+        .iter()
+        .map(|&(s, t)| reference_distances(&graph, s)[t].unwrap_or(u64::MAX))
+        .collect();
+    let checked_in: Vec<u64> = res
+        .lines()
+        .filter(|line| line.starts_with("d "))
+        .map(|line| line.split_whitespace().nth(3).unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(checked_in, expected);
+
+    for (&(s, t), &distance) in p2p.pairs.iter().zip(&expected) {
+        let (s, t) = (NodeIndex::new(s), NodeIndex::new(t));
+        assert_eq!(dijkstra_single(&graph, s, t).distances[t.index()], distance);
+        assert_eq!(
+            dial_single(&graph, s, t, max_cost).distances[t.index()],
+            distance
+        );
+        assert_eq!(
+            dijkstra_single_with::<RadixHeap, _>(&graph, s, t).distances[t.index()],
+            distance
+        );
+        assert_eq!(
+            bidirectional_dijkstra_single(&graph, s, t).distances[t.index()],
+            distance
+        );
+        for solver in solvers.iter() {
+            assert_eq!(
+                solver.solve_single(&graph, s, t).distances[t.index()],
+                distance
+            );
+            assert_eq!(solver.solve_all(&graph, s).distances[t.index()], distance);
+        }
+    }
+}
+
+#[test]
+fn co_files_are_parsed() {
+    let co = "c coordinates\np aux sp co 3\nv 1 -73530767 41085396\nv 3 10 -20\nv 2 0 0\n";
+    let co = parse_co(co).unwrap();
+    assert!(co.validate(3).is_ok());
+    assert!(co.validate(4).is_err());
+    assert_eq!(co.coordinates, [(-73530767, 41085396), (0, 0), (10, -20)]);
+}
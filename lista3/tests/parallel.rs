@@ -0,0 +1,29 @@
+use std::sync::Mutex;
+
+use indicatif::ProgressBar;
+
+use lista3::parallel::time_sources;
+
+#[test]
+fn every_source_is_run_once() {
+    let sources: Vec<usize> = (0..100).rev().collect();
+    let seen = Mutex::new(Vec::new());
+    let bar = ProgressBar::hidden();
+
+    let batch = time_sources(&sources, 4, &bar, |node| {
+        seen.lock().unwrap().push(node.index());
+    });
+
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort();
+    assert_eq!(seen, (0..100).collect::<Vec<_>>());
+    assert_eq!(batch.threads, 4);
+    assert_eq!(batch.times.len(), sources.len());
+    assert_eq!(bar.position(), 100);
+
+    let mut lines = Vec::new();
+    batch.write_to(&mut lines, &sources).unwrap();
+    let lines = String::from_utf8(lines).unwrap();
+    assert!(lines.starts_with("c threads 4 wall "));
+    assert!(lines.lines().nth(1).unwrap().starts_with("c time 100 "));
+}
@@ -0,0 +1,82 @@
+use lista3::parsing::{parse_co, parse_p2p, parse_ss, ParseError, ParseErrorKind};
+
+#[test]
+fn ss_and_p2p_files_are_parsed() {
+    let ss = parse_ss("c sources\r\np aux sp ss 2\r\ns 3\r\nc between\r\ns 1").unwrap();
+    assert_eq!(ss.sources, [2, 0]);
+    assert!(ss.validate(3).is_ok());
+    assert!(ss.validate(2).is_err());
+
+    let p2p = parse_p2p("p aux sp p2p 2\nq 1 2\n\nq 3   1\n").unwrap();
+    assert_eq!(p2p.pairs, [(0, 1), (2, 0)]);
+}
+
+#[test]
+fn malformed_ss_p2p_and_co_files_are_rejected_with_line_numbers() {
+    let ss_cases = [
+        (
+            "p aux sp ss 2\ns 1\n",
+            "line 1: the problem line declares 2 sources, but the file has 1",
+        ),
+        (
+            "p aux sp ss 1\ns 1\ns 2\n",
+            "line 3: more sources than the 1 declared on the problem line",
+        ),
+        ("p aux sp ss 1\ns 0\n", "line 2: vertex ids start at 1"),
+        ("p aux sp ss 1\ns 1 2\n", "line 2: expected 's SOURCE'"),
+        ("p aux sp ss 1\nq 1 2\n", "line 2: unknown line type 'q'"),
+        (
+            "s 1\np aux sp ss 1\n",
+            "line 1: 's' line before the problem line",
+        ),
+        ("p aux sp p2p 1\n", "line 1: expected 'p aux sp ss SOURCES'"),
+        ("", "line 1: missing problem line"),
+    ];
+    for (text, message) in ss_cases {
+        let error = parse_ss(text).unwrap_err();
+        assert_eq!(error.to_string(), message, "{:?}", text);
+    }
+
+    let p2p_cases = [
+        (
+            "p aux sp p2p 1\nq 1\n",
+            "line 2: expected 'q SOURCE TARGET'",
+        ),
+        (
+            "p aux sp p2p 1\nq 1 x\n",
+            "line 2: expected 'q SOURCE TARGET'",
+        ),
+        (
+            "p aux sp p2p 1\nq 1 2\np aux sp p2p 1\n",
+            "line 3: second problem line",
+        ),
+        (
+            "c\np aux sp p2p 3\nq 1 2\n",
+            "line 2: the problem line declares 3 pairs, but the file has 1",
+        ),
+    ];
+    for (text, message) in p2p_cases {
+        let error = parse_p2p(text).unwrap_err();
+        assert_eq!(error.to_string(), message, "{:?}", text);
+    }
+
+    assert_eq!(
+        parse_co("p aux sp co 2\nv 1 0 0\nv 3 0 0\n").err(),
+        Some(ParseError {
+            line: 3,
+            kind: ParseErrorKind::VertexOutOfRange {
+                vertex: 3,
+                nodes: 2
+            },
+        })
+    );
+    assert_eq!(
+        parse_co("p aux sp co 2\nv 1 0 0\nv 1 5 5\n").err(),
+        Some(ParseError {
+            line: 3,
+            kind: ParseErrorKind::DuplicateVertex(1),
+        })
+    );
+    let spec = parse_co("p aux sp co 2\nv 2 3 4\nv 1 -1 0\n").unwrap();
+    assert_eq!(spec.coordinates, [(-1, 0), (3, 4)]);
+}
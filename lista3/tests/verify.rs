@@ -0,0 +1,110 @@
+use petgraph::graph::NodeIndex;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+use proptest::prelude::*;
+
+use lista3::algorithms::dijkstra_all;
+use lista3::solver::{DeltaStepping, Dial, Dijkstra, Radix, ShortestPathSolver};
+use lista3::verify::{check_optimality, first_mismatch, Mismatch};
+
+mod common;
+
+use common::{build, graphs_with, Arcs};
+
+fn check<Ty: EdgeType + Sync>(graph: &Graph<(), u64, Ty>) -> Result<(), TestCaseError> {
+    let solvers: [Box<dyn ShortestPathSolver<Ty>>; 4] = [
+        Box::new(Dijkstra),
+        Box::new(Dial::for_graph(graph)),
+        Box::new(Radix),
+        Box::new(DeltaStepping::for_graph(graph)),
+    ];
+    for s in graph.node_indices() {
+        let expected = dijkstra_all(graph, s);
+        for solver in solvers.iter() {
+            let tree = solver.solve_all(graph, s);
+            prop_assert_eq!(first_mismatch(&expected.distances, &tree.distances), None);
+            prop_assert_eq!(check_optimality(graph, s, &tree), Ok(()));
+        }
+
+        // Any change to a distance breaks one of the conditions.
+        for v in graph.node_indices() {
+            let mut tree = dijkstra_all(graph, s);
+            let distance = tree.distances[v.index()];
+            tree.distances[v.index()] = match distance {
+                u64::MAX => 0,
+                0 => 1,
+                _ => distance - 1,
+            };
+            prop_assert!(check_optimality(graph, s, &tree).is_err());
+            prop_assert_eq!(
+                first_mismatch(&expected.distances, &tree.distances).map(|m| m.vertex),
+                Some(v)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn graphs() -> impl Strategy<Value = Arcs<u64>> {
+    graphs_with(1..12, 0..40, 0u64..20)
+}
+
+proptest! {
+    #[test]
+    fn solver_trees_satisfy_the_optimality_conditions((n, edges) in graphs()) {
+        check(&build::<Directed>(n, &edges))?;
+        check(&build::<Undirected>(n, &edges))?;
+    }
+}
+
+#[test]
+fn broken_trees_are_reported() {
+    let graph = build::<Directed>(3, &[(0, 1, 4), (1, 2, 1), (0, 2, 7), (2, 0, 0)]);
+    let start = NodeIndex::new(0);
+    let tree = dijkstra_all(&graph, start);
+    assert_eq!(tree.distances, [0, 4, 5]);
+
+    let mut wrong = dijkstra_all(&graph, start);
+    wrong.distances[2] = 7;
+    wrong.predecessors[2] = Some((start, graph.find_edge(start, NodeIndex::new(2)).unwrap()));
+    assert_eq!(
+        check_optimality(&graph, start, &wrong),
+        Err("arc 2 3 of cost 1 shortens the distance of 3 from 7 to 5".to_string())
+    );
+    let mismatch = first_mismatch(&tree.distances, &wrong.distances).unwrap();
+    assert_eq!(
+        mismatch,
+        Mismatch {
+            vertex: NodeIndex::new(2),
+            left: 5,
+            right: 7
+        }
+    );
+    assert_eq!(mismatch.to_string(), "vertex 3: 5 vs 7");
+
+    let mut loose = dijkstra_all(&graph, start);
+    loose.predecessors[2] = Some((start, graph.find_edge(start, NodeIndex::new(2)).unwrap()));
+    assert_eq!(
+        check_optimality(&graph, start, &loose),
+        Err("tree arc 1 3 of cost 7 is not tight: 0 + 7 != 5".to_string())
+    );
+
+    let mut orphan = dijkstra_all(&graph, start);
+    orphan.predecessors[1] = None;
+    assert_eq!(
+        check_optimality(&graph, start, &orphan),
+        Err("vertex 2 has distance 4 but no tree arc".to_string())
+    );
+
+    // Zero-cost arcs 2 -> 3 -> 2 can make tight tree arcs that never reach the source.
+    let graph = build::<Directed>(3, &[(0, 1, 1), (1, 2, 0), (2, 1, 0)]);
+    let mut cycle = dijkstra_all(&graph, start);
+    cycle.predecessors[1] = Some((
+        NodeIndex::new(2),
+        graph
+            .find_edge(NodeIndex::new(2), NodeIndex::new(1))
+            .unwrap(),
+    ));
+    assert!(check_optimality(&graph, start, &cycle)
+        .unwrap_err()
+        .contains("cycle"));
+}